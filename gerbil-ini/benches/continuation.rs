@@ -0,0 +1,59 @@
+//! Checks that joining folded continuation lines does not allocate for every line.
+//!
+//! Run with `cargo bench`.
+
+use gerbil_ini::{Ini, IniOptions};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// Allocator counting allocations and reallocations.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Parse a value folded across `lines` lines, returning the number of allocations made.
+fn bench(lines: usize) -> usize {
+    let mut ini = String::from("[Section]\nkey=start\n");
+    for _ in 0..lines {
+        ini += " continued\n";
+    }
+    let options = IniOptions { folded_continuations: true, ..Default::default() };
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let parsed = Ini::parse_with_options(&ini, &options).unwrap();
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+    let length = parsed.get_value("Section", "key").unwrap().len();
+    println!("{lines} lines: {elapsed:?}, {allocations} allocations, {length} byte value");
+    allocations
+}
+
+fn main() {
+    let small = bench(1_000);
+    let large = bench(100_000);
+
+    // The value grows geometrically, so 100 times the lines should need only a few more allocations.
+    assert!(large < small + 20, "continuation joins allocate per line");
+}
@@ -0,0 +1,35 @@
+//! Compares parsing ASCII-only input against input containing non-ASCII text.
+//!
+//! Run with `cargo bench`.
+
+use gerbil_ini::{Ini, IniMode};
+use std::time::{Duration, Instant};
+
+const ITERATIONS: u32 = 200;
+
+fn make_ini(value: &str) -> String {
+    let mut ini = String::new();
+    for section in 0..100 {
+        ini += &format!("; Section number {section}\n[Section {section}]\n\n");
+        for key in 0..50 {
+            ini += &format!("key {key}={value} {key}\n   \n");
+        }
+    }
+    ini
+}
+
+fn bench(name: &str, ini: &str) {
+    // Count the sections parsed and print them so the work cannot be optimized out.
+    let mut sections = 0;
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        sections += Ini::parse(ini, IniMode::Simple).unwrap().section_names_in_order().count();
+    }
+    let per_iteration: Duration = start.elapsed() / ITERATIONS;
+    println!("{name}: {per_iteration:?} per parse ({} bytes, {sections} sections)", ini.len());
+}
+
+fn main() {
+    bench("ascii", &make_ini("This is a value"));
+    bench("non-ascii", &make_ini("Ceci est une valeur\u{a0}à l'été"));
+}
@@ -1,7 +1,42 @@
-use alloc::borrow::ToOwned;
+use alloc::borrow::{Cow, ToOwned};
 use alloc::collections::BTreeMap;
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
 use core::fmt::{Debug, Display, Formatter};
+use core::iter::{Enumerate, Peekable};
+use core::ops::Bound;
+use core::str::{FromStr, Lines, Utf8Error};
+
+mod builder;
+pub use builder::*;
+
+mod diff;
+pub use diff::*;
+
+mod entries;
+pub use entries::*;
+
+mod load;
+pub use load::*;
+
+#[cfg(feature = "std")]
+mod net;
+#[cfg(feature = "std")]
+pub use net::*;
+
+mod reparse;
+pub use reparse::*;
+
+mod resolve;
+
+mod toml;
+
+mod tracking;
+pub use tracking::*;
+
+mod value;
+pub use value::*;
 
 const COMMENT_CHARS: &[char] = &[';', '#'];
 
@@ -11,15 +46,18 @@ const COMMENT_CHARS: &[char] = &[';', '#'];
 /// it is written for.
 ///
 /// Some ini files will successfully parse on some programs, but not so on others.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
 pub enum IniMode {
     /// Simple X=Y, where everything is passed through.
     ///
+    /// This is the default mode.
+    ///
     /// There are some restrictions to this:
     /// * Keys, values, and sections cannot be multi-line
     /// * Keys cannot contain `=` characters
     /// * Keys cannot start with `;`, `#`, or `[`
     /// * Comments must exist in their own lines with no whitespace before the comment delimiter
+    #[default]
     Simple,
 
     /// Same as `Simple`, but trim whitespace for keys and values.
@@ -32,10 +70,347 @@ pub enum IniMode {
     SimpleTrimmed
 }
 
-/// Ini parser.
+/// Describes how to handle a key that appears more than once in a section.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum DuplicateKeyPolicy {
+    /// Treat duplicate keys as an error.
+    ///
+    /// This is the default policy.
+    #[default]
+    Error,
+
+    /// Keep the last value for the key, replacing earlier ones.
+    LastWins
+}
+
+/// Describes how to handle a section title that appears more than once.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum DuplicateSectionPolicy {
+    /// Treat duplicate sections as an error.
+    ///
+    /// This is the default policy.
+    #[default]
+    Error,
+
+    /// Merge the keys of every occurrence into one section.
+    ///
+    /// Keys that appear in more than one occurrence are handled by the duplicate key policy.
+    Merge,
+
+    /// Merge the keys of every occurrence into one section, but treat a key as a duplicate if an earlier occurrence
+    /// gave it a different value. Repeating a key from an earlier occurrence with the same value is allowed.
+    MergeStrict
+}
+
+/// Describes how to handle a section with no keys.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum EmptySectionPolicy {
+    /// Allow the section.
+    ///
+    /// This is the default policy.
+    #[default]
+    Allow,
+
+    /// Allow the section, but report a warning.
+    Warn,
+
+    /// Treat the section as an error.
+    Error
+}
+
+/// Describes how to handle a `\` continuation on the last line of an ini.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum DanglingContinuationPolicy {
+    /// Return [`IniParsingError::DanglingContinuation`].
+    ///
+    /// This is the default policy.
+    #[default]
+    Error,
+
+    /// Keep the `\` as part of the line.
+    Literal
+}
+
+/// A step applied to values by [`IniOptions::value_processing`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ValueProcessingStep {
+    /// Remove whitespace from the start and end of the value.
+    Trim,
+
+    /// Remove one pair of matching `"` or `'` quotes around the value, if present.
+    Unquote
+}
+
+/// Options for parsing ini files.
+///
+/// Options other than `mode` and `comments_must_be_line_start` are off by default.
+#[derive(Copy, Clone)]
+pub struct IniOptions<'a> {
+    /// Method used for parsing.
+    pub mode: IniMode,
+
+    /// Error with [`IniParsingError::TabNotAllowed`] if any line contains a tab character.
+    pub forbid_tabs: bool,
+
+    /// Error with [`IniParsingError::TabInValue`] if a value contains a tab character.
+    ///
+    /// In [`IniMode::SimpleTrimmed`], this applies to the value after trimming.
+    pub forbid_value_tabs: bool,
+
+    /// Replace each run of whitespace in keys with a single space.
+    ///
+    /// For example, `some   KEY` is read as `some KEY`, and the two are considered duplicates.
+    pub collapse_key_whitespace: bool,
+
+    /// Trim values and replace each run of whitespace within them with a single space.
+    ///
+    /// This is useful for descriptive text, where `A   long  description` is read as `A long description`.
+    /// Escaped newlines are expanded afterwards, so they are kept when [`IniOptions::newline_escapes`] is set.
+    /// Values unquoted by [`IniOptions::quoted_values`] or [`IniOptions::strip_surrounding_quotes`] are left as
+    /// written.
+    pub collapse_value_whitespace: bool,
+
+    /// Trim values and remove one pair of matching `"` or `'` quotes around them, if present.
+    ///
+    /// Escape sequences within the quotes are not processed. See also [`IniSection::get_unquoted`].
+    pub strip_surrounding_quotes: bool,
+
+    /// Steps to apply to each value, in order.
+    ///
+    /// For example, `[Trim, Unquote]` reads `  "x"  ` as `x`, while `[Unquote, Trim]` reads it as `"x"`.
+    pub value_processing: &'a [ValueProcessingStep],
+
+    /// Read `\n` in values as a newline, and write newlines in values as `\n` when serializing.
+    ///
+    /// No other escape sequences are supported, so a value containing a literal `\n` cannot be represented.
+    pub newline_escapes: bool,
+
+    /// Allow keys before the first section title, placing them in a global section with an empty name.
+    ///
+    /// Values in the global section can be read with [`Ini::get`].
+    pub global_section: bool,
+
+    /// Allow keys before the first section title, placing them in a section with this name.
+    ///
+    /// This takes precedence over [`IniOptions::global_section`]. A later section title with the same name is treated
+    /// as a duplicate section. The name is normalized when [`IniOptions::normalize_section_names`] is set.
+    pub implicit_section_name: Option<&'a str>,
+
+    /// Allow keys to start with a comment character by escaping it with `\`.
+    ///
+    /// For example, `\;key=value` is read as the key `;key`.
+    pub escaped_comment_keys: bool,
+
+    /// Allow keys without an `=`, such as `debug`, which are read as having an empty value.
+    ///
+    /// These can be read as flags with [`IniSection::flag`].
+    pub valueless_keys: bool,
+
+    /// Treat a line starting with whitespace as a continuation of the value on the previous line, as in email headers.
+    ///
+    /// The continuation line is appended to the key's line verbatim, including its leading whitespace, before the line
+    /// is parsed, so other options apply to the whole value. Blank and comment lines are never continuations. A
+    /// continuation that does not directly follow a key or another continuation is an
+    /// [`IniParsingError::UnexpectedContinuation`] error.
+    pub folded_continuations: bool,
+
+    /// Treat a comment ending in `\` as continuing onto the next line, which is also skipped.
+    ///
+    /// This takes precedence over other handling of the next line, including [`IniOptions::folded_continuations`].
+    pub comment_continuations: bool,
+
+    /// Join a line ending in `\` with the next line, removing the `\`.
+    ///
+    /// The next line is appended verbatim, including any leading whitespace, and may itself end in `\`. Comment lines
+    /// are not continued unless [`IniOptions::comment_continuations`] is also set.
+    pub backslash_continuations: bool,
+
+    /// How to handle a `\` continuation on the last line, when there is no next line to join.
+    pub dangling_continuation: DanglingContinuationPolicy,
+
+    /// Treat an empty section title (`[]`) as the global section, which can be entered any number of times.
+    ///
+    /// This can be used alongside or instead of [`IniOptions::global_section`].
+    pub empty_title_is_global: bool,
+
+    /// Normalize section titles by trimming them, replacing each run of whitespace with a single space, and making
+    /// them lowercase.
+    ///
+    /// For example, `[ My  Server ]` is read as `[my server]`, so it is a duplicate of `[My Server]`. Other options
+    /// that name sections, such as [`IniOptions::stop_at_section`], are compared against the normalized title.
+    pub normalize_section_names: bool,
+
+    /// Values that indicate an unfilled setting, such as `CHANGEME`.
+    ///
+    /// Keys with one of these values are reported as [`IniWarning::PlaceholderValue`] by [`Ini::parse_with_report`].
+    pub placeholders: &'a [&'a str],
+
+    /// Remove carriage returns (`\r`) that appear within values rather than as part of a line ending.
+    ///
+    /// By default, they are kept verbatim.
+    pub strip_carriage_returns: bool,
+
+    /// Stop parsing when a section with this title is reached, ignoring it and everything after it.
+    pub stop_at_section: Option<&'a str>,
+
+    /// How to handle a key that appears more than once in a section.
+    pub duplicate_keys: DuplicateKeyPolicy,
+
+    /// Maximum number of times a key may be repeated in a section when duplicates are allowed.
+    ///
+    /// Exceeding this is an [`IniParsingError::LimitExceeded`] error, which limits the work done for untrusted input.
+    /// By default, there is no limit.
+    pub max_duplicates_per_key: Option<usize>,
+
+    /// Function returning how to handle duplicate keys for a given section name, overriding `duplicate_keys`.
+    ///
+    /// The function may capture state, such as a list of section names loaded at runtime. This allows, for example, a
+    /// `[servers]` section to be overridden freely while other sections stay strict.
+    pub section_duplicate_keys: Option<&'a dyn Fn(&str) -> DuplicateKeyPolicy>,
+
+    /// How to handle a section title that appears more than once.
+    pub duplicate_sections: DuplicateSectionPolicy,
+
+    /// Error with [`IniParsingError::NonCanonicalWhitespace`] if a key or value starts or ends with whitespace.
+    ///
+    /// This applies to keys and values as written, so `key = value` is an error even in [`IniMode::SimpleTrimmed`].
+    /// Keys read by [`IniOptions::valueless_keys`] are checked too.
+    pub forbid_surrounding_whitespace: bool,
+
+    /// How to handle a section title at the end of the file with no keys after it, which can be a sign of truncation.
+    pub trailing_empty_section: EmptySectionPolicy,
+
+    /// Require comment characters to be the first character of the line.
+    ///
+    /// This is on by default. If off, a comment may be indented with whitespace, so `  ; comment` is a comment
+    /// rather than a key or an error.
+    pub comments_must_be_line_start: bool,
+
+    /// Skip decorative separator lines made of three or more `=` or `-` characters, such as `=======`, as comments.
+    ///
+    /// Otherwise, a line such as `=======` is read as an empty key with the value `======`.
+    pub skip_separator_lines: bool,
+
+    /// Allow comments at the end of a value, starting with a comment character preceded by whitespace.
+    ///
+    /// For example, `key=value ; comment` has the value `value`, but `key=a;b` has the value `a;b`.
+    pub inline_comments: bool,
+
+    /// Read values wrapped in matching `"` or `'` quotes literally, without the quotes.
+    ///
+    /// This protects comment characters within the quotes from [`IniOptions::inline_comments`], so
+    /// `key="a ; b" ; comment` has the value `a ; b`. Escape sequences within the quotes are not processed.
+    pub quoted_values: bool
+}
+
+impl Default for IniOptions<'_> {
+    fn default() -> Self {
+        Self {
+            mode: IniMode::default(),
+            forbid_tabs: false,
+            forbid_value_tabs: false,
+            collapse_key_whitespace: false,
+            collapse_value_whitespace: false,
+            strip_surrounding_quotes: false,
+            value_processing: &[],
+            newline_escapes: false,
+            global_section: false,
+            implicit_section_name: None,
+            escaped_comment_keys: false,
+            valueless_keys: false,
+            folded_continuations: false,
+            comment_continuations: false,
+            backslash_continuations: false,
+            dangling_continuation: DanglingContinuationPolicy::default(),
+            empty_title_is_global: false,
+            normalize_section_names: false,
+            placeholders: &[],
+            strip_carriage_returns: false,
+            stop_at_section: None,
+            duplicate_keys: DuplicateKeyPolicy::default(),
+            max_duplicates_per_key: None,
+            section_duplicate_keys: None,
+            duplicate_sections: DuplicateSectionPolicy::default(),
+            forbid_surrounding_whitespace: false,
+            trailing_empty_section: EmptySectionPolicy::default(),
+            comments_must_be_line_start: true,
+            skip_separator_lines: false,
+            inline_comments: false,
+            quoted_values: false
+        }
+    }
+}
+
+impl Debug for IniOptions<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("IniOptions")
+            .field("mode", &self.mode)
+            .field("forbid_tabs", &self.forbid_tabs)
+            .field("forbid_value_tabs", &self.forbid_value_tabs)
+            .field("collapse_key_whitespace", &self.collapse_key_whitespace)
+            .field("collapse_value_whitespace", &self.collapse_value_whitespace)
+            .field("strip_surrounding_quotes", &self.strip_surrounding_quotes)
+            .field("value_processing", &self.value_processing)
+            .field("newline_escapes", &self.newline_escapes)
+            .field("global_section", &self.global_section)
+            .field("implicit_section_name", &self.implicit_section_name)
+            .field("escaped_comment_keys", &self.escaped_comment_keys)
+            .field("valueless_keys", &self.valueless_keys)
+            .field("folded_continuations", &self.folded_continuations)
+            .field("comment_continuations", &self.comment_continuations)
+            .field("backslash_continuations", &self.backslash_continuations)
+            .field("dangling_continuation", &self.dangling_continuation)
+            .field("empty_title_is_global", &self.empty_title_is_global)
+            .field("normalize_section_names", &self.normalize_section_names)
+            .field("placeholders", &self.placeholders)
+            .field("strip_carriage_returns", &self.strip_carriage_returns)
+            .field("stop_at_section", &self.stop_at_section)
+            .field("duplicate_keys", &self.duplicate_keys)
+            .field("max_duplicates_per_key", &self.max_duplicates_per_key)
+            .field("section_duplicate_keys", &self.section_duplicate_keys.map(|_| "Fn(&str) -> DuplicateKeyPolicy"))
+            .field("duplicate_sections", &self.duplicate_sections)
+            .field("forbid_surrounding_whitespace", &self.forbid_surrounding_whitespace)
+            .field("trailing_empty_section", &self.trailing_empty_section)
+            .field("comments_must_be_line_start", &self.comments_must_be_line_start)
+            .field("skip_separator_lines", &self.skip_separator_lines)
+            .field("inline_comments", &self.inline_comments)
+            .field("quoted_values", &self.quoted_values)
+            .finish()
+    }
+}
+
+impl From<IniMode> for IniOptions<'_> {
+    fn from(mode: IniMode) -> Self {
+        Self { mode, ..Default::default() }
+    }
+}
+
+/// Information gathered while parsing an ini, returned by [`Ini::parse_with_report`].
 #[derive(Clone, Debug, PartialEq, Default)]
+pub struct IniParseReport {
+    /// Number of comment lines that were skipped.
+    pub comment_lines: usize,
+
+    /// Number of empty or whitespace-only lines that were skipped.
+    pub blank_lines: usize,
+
+    /// Warnings about content that was parsed successfully but may be a mistake.
+    pub warnings: Vec<IniWarning>
+}
+
+/// Ini parser.
+///
+/// Two inis are equal if they have the same sections and values, regardless of the order sections were added in.
+#[derive(Clone, Debug, Default)]
 pub struct Ini {
-    sections: BTreeMap<String, IniSection>
+    sections: BTreeMap<String, IniSection>,
+    section_order: Vec<String>
+}
+
+impl PartialEq for Ini {
+    fn eq(&self, other: &Self) -> bool {
+        self.sections == other.sections
+    }
 }
 
 /// Section for an ini.
@@ -47,10 +422,59 @@ pub struct IniSection {
 impl Ini {
     /// Parse the ini.
     pub fn parse(string: &str, config: IniMode) -> Result<Self, IniParsingError> {
-        match config {
-            IniMode::Simple => Self::parse_simple(string, config),
-            IniMode::SimpleTrimmed => Self::parse_simple(string, config),
+        Self::parse_with_options(string, &config.into())
+    }
+
+    /// Parse the ini with additional options.
+    pub fn parse_with_options(string: &str, options: &IniOptions) -> Result<Self, IniParsingError> {
+        Self::parse_with_report(string, options).map(|(ini, _)| ini)
+    }
+
+    /// Check that the ini is well-formed without building an [`Ini`], returning the first error found.
+    ///
+    /// Each line is checked as it would be by [`Ini::parse_with_options`], and keys must follow a section title unless
+    /// a global or implicit section is enabled. Errors that depend on the contents of earlier sections, such as
+    /// duplicate sections and keys, are not checked.
+    pub fn quick_check(string: &str, options: &IniOptions) -> Result<(), IniParsingError> {
+        let mut in_section = options.global_section || options.implicit_section_name.is_some();
+        let mut empty_section = None;
+
+        for logical_line in LogicalLines::new(string, *options) {
+            let LogicalLine { line_number, text, .. } = logical_line?;
+            let line = text.as_ref();
+            match parse_line(line, line_number, options)? {
+                Line::Blank | Line::Comment => (),
+                Line::Section(title) if options.stop_at_section == Some(title.as_str()) => break,
+                Line::Section(title) => {
+                    in_section = true;
+                    empty_section = Some((line_number, title));
+                },
+                Line::KeyValue(..) if !in_section => return Err(IniParsingError::ExpectedSectionTitle { line_number }),
+                Line::KeyValue(..) => empty_section = None
+            }
         }
+
+        match (empty_section, options.trailing_empty_section) {
+            (Some((line_number, section)), EmptySectionPolicy::Error) => Err(IniParsingError::TrailingEmptySection { line_number, section }),
+            _ => Ok(())
+        }
+    }
+
+    /// Parse the ini until a section titled `stop_section` is reached, ignoring it and everything after it.
+    ///
+    /// This is a shortcut for setting [`IniOptions::stop_at_section`], and overrides it if it is already set.
+    pub fn parse_until(string: &str, stop_section: &str, options: &IniOptions) -> Result<Self, IniParsingError> {
+        Self::parse_with_options(string, &IniOptions { stop_at_section: Some(stop_section), ..*options })
+    }
+
+    /// Parse the ini with additional options, also returning a report with information about the file.
+    pub fn parse_with_report(string: &str, options: &IniOptions) -> Result<(Self, IniParseReport), IniParsingError> {
+        let mut report = IniParseReport::default();
+        let ini = match options.mode {
+            IniMode::Simple => Self::parse_simple(string, options, &mut report),
+            IniMode::SimpleTrimmed => Self::parse_simple(string, options, &mut report),
+        }?;
+        Ok((ini, report))
     }
 
     /// Get the section.
@@ -60,6 +484,23 @@ impl Ini {
         self.sections.get(section)
     }
 
+    /// Get the section at `index`, in order of name.
+    ///
+    /// Returns the section name and section, or `None` if `index` is out of bounds. This is O(n).
+    pub fn section_at(&self, index: usize) -> Option<(&str, &IniSection)> {
+        self.sections.iter().nth(index).map(|(name, section)| (name.as_str(), section))
+    }
+
+    /// Get the value in the global section of the ini.
+    ///
+    /// The global section is the section with an empty name, which holds keys that come before the first section
+    /// title when [`IniOptions::global_section`] is enabled.
+    ///
+    /// Returns `None` if there is no global section or the key does not exist.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.get_value("", key)
+    }
+
     /// Get the value in the section of the ini.
     ///
     /// Returns `None` if the section or key do not exist.
@@ -67,93 +508,1094 @@ impl Ini {
         self.get_section(section).and_then(|s| s.get(key))
     }
 
-    fn parse_simple(string: &str, config: IniMode) -> Result<Self, IniParsingError> {
-        let mut ini = Ini::default();
+    /// Get the value in the section of the ini as UTF-8 bytes.
+    ///
+    /// Returns `None` if the section or key do not exist.
+    pub fn get_value_bytes(&self, section: &str, key: &str) -> Option<&[u8]> {
+        self.get_value(section, key).map(str::as_bytes)
+    }
 
-        let mut lines = string.lines().enumerate().map(|(line_index, line)| (line_index + 1, line));
-        let mut section = None;
+    /// Find the first section containing `key`, with sections checked in order of name.
+    ///
+    /// Returns the section name and value, or `None` if no section contains the key.
+    pub fn get_any(&self, key: &str) -> Option<(&str, &str)> {
+        self.sections.iter().find_map(|(name, section)| section.get(key).map(|value| (name.as_str(), value)))
+    }
+
+    /// Returns `true` if both inis have the same sections, and the sections are equal according to
+    /// [`IniSection::eq_trimmed`].
+    ///
+    /// This can compare inis parsed with [`IniMode::Simple`] and [`IniMode::SimpleTrimmed`], as long as keys are not
+    /// followed by whitespace.
+    pub fn equivalent(&self, other: &Ini) -> bool {
+        self.sections.len() == other.sections.len()
+            && self.sections.iter().zip(&other.sections).all(|((name, section), (other_name, other_section))| name == other_name && section.eq_trimmed(other_section))
+    }
+
+    /// Get the depth of the most deeply nested section, treating `.` as a separator for nested sections.
+    ///
+    /// For example, `a` has a depth of 1 and `a.b.c` has a depth of 3. Returns 0 if there are no sections.
+    pub fn max_section_depth(&self) -> usize {
+        self.sections.keys().map(|name| name.matches('.').count() + 1).max().unwrap_or(0)
+    }
+
+    /// Compute a fingerprint of the sections, keys, and values in the ini.
+    ///
+    /// This is a 64-bit FNV-1a hash, so it is the same across runs and platforms and can be stored to cheaply detect
+    /// changes. Like equality, it does not depend on the order sections or keys were added in.
+    pub fn fingerprint(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const FNV_PRIME: u64 = 0x100000001b3;
+
+        fn write(hash: &mut u64, bytes: &[u8]) {
+            for &b in bytes {
+                *hash = (*hash ^ b as u64).wrapping_mul(FNV_PRIME);
+            }
+        }
+
+        // Prefix each string with its length so that boundaries between strings are unambiguous.
+        fn write_str(hash: &mut u64, string: &str) {
+            write(hash, &(string.len() as u64).to_le_bytes());
+            write(hash, string.as_bytes());
+        }
+
+        let mut hash = FNV_OFFSET_BASIS;
+        for (name, section) in &self.sections {
+            write_str(&mut hash, name);
+            write(&mut hash, &(section.values.len() as u64).to_le_bytes());
+            for (key, value) in &section.values {
+                write_str(&mut hash, key);
+                write_str(&mut hash, value);
+            }
+        }
+        hash
+    }
+
+    /// Check that every section and key in the ini is allowed.
+    ///
+    /// `allowed` maps each allowed section name to the keys allowed in it. Returns every unknown section and key
+    /// otherwise. Keys within an unknown section are not reported individually.
+    pub fn validate_keys(&self, allowed: &BTreeMap<&str, &[&str]>) -> Result<(), Vec<UnknownKey>> {
+        let mut unknown = Vec::new();
 
-        while let Some((line_number, line)) = lines.next() {
-            if line.chars().next().iter().any(|i| COMMENT_CHARS.contains(i)) || line.is_empty() || line.chars().all(|c| c.is_whitespace()) {
+        for (name, section) in &self.sections {
+            let Some(allowed_keys) = allowed.get(name.as_str()) else {
+                unknown.push(UnknownKey::Section { section: name.to_owned() });
                 continue
+            };
+
+            for key in section.values.keys() {
+                if !allowed_keys.contains(&key.as_str()) {
+                    unknown.push(UnknownKey::Key { section: name.to_owned(), key: key.to_owned() });
+                }
             }
+        }
+
+        if unknown.is_empty() {
+            Ok(())
+        }
+        else {
+            Err(unknown)
+        }
+    }
+
+    /// Get the value in the section of the ini, parsed as `T`.
+    ///
+    /// Returns `default` if the section or key do not exist, or if the value could not be parsed.
+    pub fn get_or<T: FromStr>(&self, section: &str, key: &str, default: T) -> T {
+        self.get_value(section, key).and_then(|v| v.parse().ok()).unwrap_or(default)
+    }
 
-            if line.starts_with('[') {
-                let end = line.find(']').ok_or(IniParsingError::BrokenSectionTitle { line_number })?;
-                let title = line[1..end].to_owned();
-                if ini.sections.contains_key(&title) {
-                    return Err(IniParsingError::DuplicateSection { line_number, section: title })
+    /// Set the value in the section of the ini, creating the section if it does not exist.
+    ///
+    /// Returns the previous value, if any.
+    pub fn set_value(&mut self, section: &str, key: &str, value: &str) -> Option<String> {
+        self.section_or_insert(section).set(key, value)
+    }
+
+    /// Merge the sections and keys of `other` into the ini, adding sections and keys that do not exist yet.
+    ///
+    /// If a key exists in both with different values, `resolver` is called with the section name, key, existing value,
+    /// and incoming value, and the value it returns is used.
+    pub fn merge_with(&mut self, other: &Ini, mut resolver: impl FnMut(&str, &str, &str, &str) -> String) {
+        for name in other.section_names_in_order() {
+            let incoming = &other.sections[name];
+            let section = self.section_or_insert(name);
+            for (key, value) in &incoming.values {
+                match section.values.get_mut(key) {
+                    Some(existing) if existing != value => *existing = resolver(name, key, existing, value),
+                    Some(_) => (),
+                    None => { section.values.insert(key.clone(), value.clone()); }
                 }
-                section = Some(title.clone());
-                ini.sections.insert(title, Default::default());
-                continue
+            }
+        }
+    }
+
+    /// Iterate over every section whose name starts with `prefix`, in order of name.
+    pub fn sections_with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = (&'a str, &'a IniSection)> {
+        self.sections
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(move |(name, _)| name.starts_with(prefix))
+            .map(|(name, section)| (name.as_str(), section))
+    }
+
+    /// Iterate mutably over every section whose name starts with `prefix`, in order of name.
+    pub fn sections_with_prefix_mut<'a>(&'a mut self, prefix: &'a str) -> impl Iterator<Item = (&'a str, &'a mut IniSection)> {
+        self.sections
+            .range_mut::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(move |(name, _)| name.starts_with(prefix))
+            .map(|(name, section)| (name.as_str(), section))
+    }
+
+    /// Remove every section whose name does not start with `prefix`.
+    pub fn retain_prefix(&mut self, prefix: &str) {
+        self.sections.retain(|name, _| name.starts_with(prefix));
+        self.section_order.retain(|name| name.starts_with(prefix));
+    }
+
+    /// Remove keys with empty or whitespace-only values from every section.
+    ///
+    /// Sections are kept even if they become empty.
+    pub fn remove_empty(&mut self) {
+        self.sections.values_mut().for_each(IniSection::remove_empty);
+    }
+
+    /// Get the names of all sections in the order they were added, such as the order they appear in the parsed file.
+    pub fn section_names_in_order(&self) -> impl Iterator<Item = &str> {
+        self.section_order.iter().map(String::as_str)
+    }
+
+    fn section_or_insert(&mut self, name: &str) -> &mut IniSection {
+        if !self.sections.contains_key(name) {
+            self.section_order.push(name.to_owned());
+        }
+        self.sections.entry(name.to_owned()).or_default()
+    }
+
+    /// Apply an override in the form of `section.key=value`, creating the section if it does not exist.
+    ///
+    /// The section name is everything before the last `.` preceding the `=`, so section names may contain `.`
+    /// characters but keys may not. The key and value are used verbatim.
+    pub fn apply_override(&mut self, spec: &str) -> Result<(), OverrideError> {
+        let equals = spec.find('=').ok_or_else(|| OverrideError::MissingEquals { spec: spec.to_owned() })?;
+        let (path, value_eq) = spec.split_at(equals);
+        let dot = path.rfind('.').ok_or_else(|| OverrideError::MissingSection { spec: spec.to_owned() })?;
+        self.set_value(&path[..dot], &path[dot + 1..], &value_eq[1..]);
+        Ok(())
+    }
+
+    /// Apply multiple overrides with [`Ini::apply_override`].
+    ///
+    /// Overrides are applied in order, stopping at the first invalid override. Overrides before it remain applied.
+    pub fn apply_overrides<'a, I: IntoIterator<Item = &'a str>>(&mut self, specs: I) -> Result<(), OverrideError> {
+        specs.into_iter().try_for_each(|spec| self.apply_override(spec))
+    }
+
+    /// Serialize the ini to a string.
+    ///
+    /// Sections are written in order, separated by a blank line. Each section is written with
+    /// [`IniSection::serialize`].
+    pub fn serialize(&self, mode: IniMode) -> String {
+        self.serialize_with_options(&mode.into())
+    }
+
+    /// Serialize the ini to a string with additional options.
+    ///
+    /// Only [`IniOptions::mode`] and [`IniOptions::newline_escapes`] are applied. Values are written as-is otherwise,
+    /// so output may not read back the same under options such as [`IniOptions::inline_comments`] or
+    /// [`IniOptions::quoted_values`].
+    pub fn serialize_with_options(&self, options: &IniOptions) -> String {
+        let mut output = String::new();
+        for (name, section) in &self.sections {
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            section.serialize_into(name, options, &mut output);
+        }
+        output
+    }
+
+    fn parse_simple(string: &str, options: &IniOptions, report: &mut IniParseReport) -> Result<Self, IniParsingError> {
+        let mut ini = Ini::default();
+
+        let mut section = None;
+        let mut earlier_values = BTreeMap::new();
+        let mut empty_title_line = None;
+        let mut duplicate_counts = BTreeMap::new();
+
+        for logical_line in LogicalLines::new(string, *options) {
+            let LogicalLine { line_number, line_count, text } = logical_line?;
+            let line = text.as_ref();
+            let (key, value) = match parse_line(line, line_number, options)? {
+                Line::Blank => {
+                    report.blank_lines += 1;
+                    continue
+                },
+                Line::Comment => {
+                    report.comment_lines += line_count;
+                    continue
+                },
+                Line::Section(title) if options.stop_at_section == Some(title.as_str()) => break,
+                Line::Section(title) if title.is_empty() && options.empty_title_is_global => {
+                    earlier_values.clear();
+                    empty_title_line = Some(line_number);
+                    ini.section_or_insert("");
+                    section = Some(title);
+                    continue
+                },
+                Line::Section(title) => {
+                    earlier_values.clear();
+                    empty_title_line = Some(line_number);
+                    match (ini.sections.get(&title), options.duplicate_sections) {
+                        (None, _) => { ini.section_or_insert(&title); },
+                        (Some(_), DuplicateSectionPolicy::Error) => return Err(IniParsingError::DuplicateSection { line_number, section: title }),
+                        (Some(_), DuplicateSectionPolicy::Merge) => (),
+                        (Some(existing), DuplicateSectionPolicy::MergeStrict) => earlier_values = existing.values.clone()
+                    }
+                    section = Some(title);
+                    continue
+                },
+                Line::KeyValue(key, value) => (key, value)
+            };
+
+            if let (None, Some(name)) = (section.as_ref(), options.implicit_section_name) {
+                let name = if options.normalize_section_names { normalize_section_name(name) } else { name.to_owned() };
+                ini.section_or_insert(&name);
+                section = Some(name);
+            }
+            else if section.is_none() && options.global_section {
+                section = Some(String::new());
+                ini.section_or_insert("");
             }
 
             let Some(section) = section.as_ref() else {
                 return Err(IniParsingError::ExpectedSectionTitle { line_number })
             };
+            empty_title_line = None;
 
-            let l = line.find('=').ok_or(IniParsingError::MissingEquals { line_number })?;
-            let (key_str, value_eq) = line.split_at(l);
-            let value_str = &value_eq[1..];
-
-            let key: String;
-            let value: String;
+            if options.placeholders.contains(&value.as_str()) {
+                report.warnings.push(IniWarning::PlaceholderValue { line_number, key: key.clone() });
+            }
 
-            match config {
-                IniMode::Simple => {
-                    key = key_str.to_owned();
-                    value = value_str.to_owned();
-                }
-                IniMode::SimpleTrimmed => {
-                    key = key_str.trim_end().to_owned();
-                    value = value_str.trim_start().to_owned();
-                }
+            // Only the first repeat of an earlier value is skipped, so later repeats still follow the key policy.
+            match earlier_values.remove(&key) {
+                Some(earlier) if earlier != value => return Err(IniParsingError::DuplicateSectionKey { line_number, section: section.to_string(), key }),
+                Some(_) => continue,
+                None => ()
             }
 
+            let duplicate_keys = options.section_duplicate_keys.map_or(options.duplicate_keys, |f| f(section));
             let s = ini.sections.get_mut(section).unwrap();
-            if s.values.contains_key(&key) {
+            if duplicate_keys == DuplicateKeyPolicy::Error && s.values.contains_key(&key) {
                 return Err(IniParsingError::DuplicateSectionKey { line_number, section: section.to_string(), key })
             }
+            if let (Some(max), true) = (options.max_duplicates_per_key, s.values.contains_key(&key)) {
+                let count = duplicate_counts.entry((section.to_owned(), key.clone())).or_insert(0usize);
+                *count += 1;
+                if *count > max {
+                    return Err(IniParsingError::LimitExceeded { line_number, section: section.to_string(), key })
+                }
+            }
             s.values.insert(key, value);
         }
 
+        if let (Some(line_number), Some(section)) = (empty_title_line, section) {
+            match options.trailing_empty_section {
+                EmptySectionPolicy::Allow => (),
+                EmptySectionPolicy::Warn => report.warnings.push(IniWarning::TrailingEmptySection { line_number, section }),
+                EmptySectionPolicy::Error => return Err(IniParsingError::TrailingEmptySection { line_number, section })
+            }
+        }
+
         Ok(ini)
     }
 }
 
-impl IniSection {
-    /// Get the value for a key.
-    ///
-    /// Returns `None` if the key is not present.
-    pub fn get(&self, key: &str) -> Option<&str> {
-        self.values.get(key).map(String::as_str)
-    }
+/// A line of an ini after joining continued lines.
+pub(crate) struct LogicalLine<'a> {
+    /// Line number of the first line.
+    pub(crate) line_number: usize,
+
+    /// Number of lines that were joined.
+    pub(crate) line_count: usize,
+
+    pub(crate) text: Cow<'a, str>
 }
 
-/// An error generated by the ini parser.
-#[derive(Clone, PartialEq)]
-pub enum IniParsingError {
-    MissingEquals { line_number: usize },
-    ExpectedSectionTitle { line_number: usize },
-    BrokenSectionTitle { line_number: usize },
-    DuplicateSection { line_number: usize, section: String },
-    DuplicateSectionKey { line_number: usize, section: String, key: String },
+/// Iterator over the lines of an ini, joining lines continued with [`IniOptions::backslash_continuations`],
+/// [`IniOptions::comment_continuations`], and [`IniOptions::folded_continuations`].
+#[derive(Clone, Debug)]
+pub(crate) struct LogicalLines<'a> {
+    lines: Peekable<Enumerate<Lines<'a>>>,
+    options: IniOptions<'a>
 }
 
-impl Display for IniParsingError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        match self {
-            Self::MissingEquals { line_number } => f.write_fmt(format_args!("Parsing error on line {line_number}: Missing an `=` to separate the key and value")),
-            Self::ExpectedSectionTitle { line_number } => f.write_fmt(format_args!("Parsing error on line {line_number}: Expected a section title")),
-            Self::BrokenSectionTitle { line_number } => f.write_fmt(format_args!("Parsing error on line {line_number}: Expected a `]` to close a `[`")),
-            Self::DuplicateSection { line_number, section } => f.write_fmt(format_args!("Parsing error on line {line_number}: Duplicate section `{section}`")),
-            Self::DuplicateSectionKey { line_number, section, key } => f.write_fmt(format_args!("Parsing error on line {line_number}: Duplicate key `{key}` in section `{section}`"))
-        }
+impl<'a> LogicalLines<'a> {
+    pub(crate) fn new(string: &'a str, options: IniOptions<'a>) -> Self {
+        Self { lines: string.lines().enumerate().peekable(), options }
     }
 }
 
-impl Debug for IniParsingError {
+impl<'a> Iterator for LogicalLines<'a> {
+    type Item = Result<LogicalLine<'a>, IniParsingError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (line_index, first) = self.lines.next()?;
+        let line_number = line_index + 1;
+        if is_folded_line(first, &self.options) {
+            return Some(Err(IniParsingError::UnexpectedContinuation { line_number }))
+        }
+
+        let comment = is_any_comment_line(first, &self.options);
+        let continues = if comment { self.options.comment_continuations } else { self.options.backslash_continuations };
+        let folds = !comment && !first.starts_with('[') && !is_skipped_line(first, &self.options);
+
+        let mut text = Cow::Borrowed(first);
+        let mut line_count = 1;
+        let mut last_line_number = line_number;
+        loop {
+            let options = &self.options;
+            if continues && text.ends_with('\\') {
+                text.to_mut().pop();
+                let Some((line_index, line)) = self.lines.next() else {
+                    if comment {
+                        break
+                    }
+                    match options.dangling_continuation {
+                        DanglingContinuationPolicy::Error => return Some(Err(IniParsingError::DanglingContinuation { line_number: last_line_number })),
+                        DanglingContinuationPolicy::Literal => text.to_mut().push('\\')
+                    }
+                    break
+                };
+                text.to_mut().push_str(line);
+                line_count += 1;
+                last_line_number = line_index + 1;
+            }
+            else if folds && self.lines.peek().map_or(false, |(_, line)| is_folded_line(line, options)) {
+                let (line_index, line) = self.lines.next().unwrap();
+                text.to_mut().push_str(line);
+                line_count += 1;
+                last_line_number = line_index + 1;
+            }
+            else {
+                break
+            }
+        }
+
+        Some(Ok(LogicalLine { line_number, line_count, text }))
+    }
+}
+
+enum Line {
+    Blank,
+    Comment,
+    Section(String),
+    KeyValue(String, String)
+}
+
+fn parse_line(line: &str, line_number: usize, options: &IniOptions) -> Result<Line, IniParsingError> {
+    if options.forbid_tabs && line.contains('\t') {
+        return Err(IniParsingError::TabNotAllowed { line_number })
+    }
+
+    if is_blank_line(line) {
+        return Ok(Line::Blank)
+    }
+
+    if options.skip_separator_lines && is_separator_line(line) {
+        return Ok(Line::Comment)
+    }
+
+    if is_any_comment_line(line, options) {
+        return Ok(Line::Comment)
+    }
+
+    let line = match line.strip_prefix('\\') {
+        Some(escaped) if options.escaped_comment_keys && is_comment_line(escaped) => escaped,
+        _ => line
+    };
+
+    if line.starts_with('[') {
+        let end = line.find(']').ok_or(IniParsingError::BrokenSectionTitle { line_number })?;
+        let title = &line[1..end];
+        if options.normalize_section_names {
+            return Ok(Line::Section(normalize_section_name(title)))
+        }
+        return Ok(Line::Section(title.to_owned()))
+    }
+
+    let parts = line.split_once('=').or_else(|| options.valueless_keys.then_some((line, "")));
+    if let (true, Some((key, value))) = (options.forbid_surrounding_whitespace, parts) {
+        if key.trim() != key || value.trim() != value {
+            return Err(IniParsingError::NonCanonicalWhitespace { line_number })
+        }
+    }
+
+    let (mut key, mut value) = if options.valueless_keys && !line.contains('=') {
+        let mut key = line.to_owned();
+        if options.inline_comments {
+            strip_inline_comment(&mut key, false);
+        }
+        if options.mode == IniMode::SimpleTrimmed {
+            key.truncate(key.trim_end().len());
+        }
+        (key, String::new())
+    }
+    else {
+        parse_key_value(line, line_number, options.mode)?
+    };
+    if options.collapse_key_whitespace {
+        key = collapse_whitespace(&key);
+    }
+    let mut quoted = false;
+    match options.quoted_values.then(|| quoted_value(&value, options.inline_comments)).flatten() {
+        Some(inner) => {
+            value = inner.to_owned();
+            quoted = true;
+        },
+        None if options.inline_comments => {
+            // The value may have been trimmed already, so check the line for whitespace after the `=`.
+            let after_whitespace = line.split_once('=').map_or(false, |(_, v)| v.starts_with(char::is_whitespace));
+            strip_inline_comment(&mut value, after_whitespace)
+        },
+        None => ()
+    }
+    if options.strip_carriage_returns {
+        value.retain(|c| c != '\r');
+    }
+    if options.forbid_value_tabs && value.contains('\t') {
+        return Err(IniParsingError::TabInValue { line_number })
+    }
+    if options.strip_surrounding_quotes {
+        let trimmed = value.trim();
+        let inner = unquote(trimmed);
+        quoted |= inner.len() != trimmed.len();
+        value = inner.to_owned();
+    }
+    if options.collapse_value_whitespace && !quoted {
+        value = collapse_whitespace(value.trim());
+    }
+    for step in options.value_processing {
+        value = match step {
+            ValueProcessingStep::Trim => value.trim(),
+            ValueProcessingStep::Unquote => unquote(&value)
+        }.to_owned();
+    }
+    if options.newline_escapes {
+        value = value.replace("\\n", "\n");
+    }
+
+    Ok(Line::KeyValue(key, value))
+}
+
+fn is_comment_line(line: &str) -> bool {
+    // Comment characters are ASCII, so checking the first byte is equivalent to checking the first character.
+    line.as_bytes().first().map_or(false, |&b| COMMENT_CHARS.contains(&(b as char)))
+}
+
+fn is_any_comment_line(line: &str, options: &IniOptions) -> bool {
+    is_comment_line(line) || (!options.comments_must_be_line_start && is_comment_line(line.trim_start()))
+}
+
+/// Returns `true` for blank, comment, and separator lines, which are never continuations.
+fn is_skipped_line(line: &str, options: &IniOptions) -> bool {
+    is_blank_line(line) || is_any_comment_line(line, options) || (options.skip_separator_lines && is_separator_line(line))
+}
+
+fn is_folded_line(line: &str, options: &IniOptions) -> bool {
+    options.folded_continuations && line.starts_with(char::is_whitespace) && !is_skipped_line(line, options)
+}
+
+fn is_separator_line(line: &str) -> bool {
+    let line = line.trim();
+    line.len() >= 3 && line.bytes().all(|b| b == b'=' || b == b'-')
+}
+
+fn is_blank_line(line: &str) -> bool {
+    // Check bytes while the line is ASCII, as `char::is_whitespace` only accepts these ASCII characters, then fall back
+    // to checking characters from the first non-ASCII byte.
+    let bytes = line.as_bytes();
+    match bytes.iter().position(|b| !matches!(b, b'\t'..=b'\r' | b' ')) {
+        None => true,
+        Some(i) if bytes[i].is_ascii() => false,
+        Some(i) => line[i..].chars().all(char::is_whitespace)
+    }
+}
+
+fn quoted_value(value: &str, inline_comments: bool) -> Option<&str> {
+    let value = value.trim();
+    let quote = value.chars().next().filter(|c| ['"', '\''].contains(c))?;
+    let rest = &value[1..];
+    let end = rest.find(quote)?;
+    let after = &rest[end + 1..];
+    let after_comment = inline_comments && after.starts_with(char::is_whitespace) && is_comment_line(after.trim_start());
+    (after.is_empty() || after_comment).then(|| &rest[..end])
+}
+
+fn strip_inline_comment(value: &mut String, after_whitespace: bool) {
+    let mut previous_whitespace = after_whitespace;
+    let start = value.char_indices().find_map(|(index, c)| {
+        let found = previous_whitespace && COMMENT_CHARS.contains(&c);
+        previous_whitespace = c.is_whitespace();
+        found.then_some(index)
+    });
+    if let Some(start) = start {
+        value.truncate(value[..start].trim_end().len());
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    ['"', '\'']
+        .iter()
+        .find_map(|&quote| value.strip_prefix(quote).and_then(|v| v.strip_suffix(quote)))
+        .unwrap_or(value)
+}
+
+fn normalize_section_name(name: &str) -> String {
+    collapse_whitespace(name.trim()).to_lowercase()
+}
+
+fn collapse_whitespace(string: &str) -> String {
+    let mut collapsed = String::with_capacity(string.len());
+    let mut last_whitespace = false;
+    for c in string.chars() {
+        let whitespace = c.is_whitespace();
+        if !whitespace {
+            collapsed.push(c);
+        }
+        else if !last_whitespace {
+            collapsed.push(' ');
+        }
+        last_whitespace = whitespace;
+    }
+    collapsed
+}
+
+fn parse_key_value(line: &str, line_number: usize, config: IniMode) -> Result<(String, String), IniParsingError> {
+    let l = line.find('=').ok_or(IniParsingError::MissingEquals { line_number })?;
+    let (key_str, value_eq) = line.split_at(l);
+    let value_str = &value_eq[1..];
+
+    match config {
+        IniMode::Simple => Ok((key_str.to_owned(), value_str.to_owned())),
+        IniMode::SimpleTrimmed => Ok((key_str.trim_end().to_owned(), value_str.trim_start().to_owned()))
+    }
+}
+
+impl IniSection {
+    /// Get the value for a key.
+    ///
+    /// Returns `None` if the key is not present.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    /// Get the stored key and its value.
+    ///
+    /// Keys are currently matched exactly, so the stored key is always equal to `key`.
+    ///
+    /// Returns `None` if the key is not present.
+    pub fn get_key_value(&self, key: &str) -> Option<(&str, &str)> {
+        self.values.get_key_value(key).map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Get the value for a key, or `default` if the key is not present.
+    ///
+    /// The returned `bool` is `true` if `default` was used.
+    pub fn get_or_tracked<'a>(&'a self, key: &str, default: &'a str) -> (&'a str, bool) {
+        match self.get(key) {
+            Some(value) => (value, false),
+            None => (default, true)
+        }
+    }
+
+    /// Check whether a flag is set.
+    ///
+    /// Returns `true` if the key is present with an empty value, such as a key read with
+    /// [`IniOptions::valueless_keys`], or with a value accepted as `true` by [`IniValueRef::as_bool`]. Returns `false`
+    /// otherwise, including if the key is not present.
+    pub fn flag(&self, key: &str) -> bool {
+        self.value(key).map_or(false, |v| v.as_str().is_empty() || v.as_bool() == Ok(true))
+    }
+
+    /// Get the value for a key with one pair of matching `"` or `'` quotes around it removed.
+    ///
+    /// Values that are not quoted, or whose quotes do not match, are returned unchanged. Escape sequences within the
+    /// quotes are not processed.
+    ///
+    /// Returns `None` if the key is not present.
+    pub fn get_unquoted(&self, key: &str) -> Option<&str> {
+        self.get(key).map(unquote)
+    }
+
+    /// Get the value for a key, borrowed if possible.
+    ///
+    /// Values are currently always unescaped while parsing, so this always returns [`Cow::Borrowed`]. Prefer this
+    /// over [`IniSection::get`] where values may need to be decoded on access in the future.
+    ///
+    /// Returns `None` if the key is not present.
+    pub fn get_cow(&self, key: &str) -> Option<Cow<'_, str>> {
+        self.get(key).map(Cow::Borrowed)
+    }
+
+    /// Get the value for a key parsed as `T`.
+    ///
+    /// If the value cannot be parsed, the error contains the key and value. Returns `None` if the key is not present.
+    pub fn get_parsed_ctx<T: FromStr>(&self, key: &str) -> Option<Result<T, ParseFieldError>> {
+        self.get(key).map(|value| value.parse().map_err(|_| ParseFieldError { key: key.to_owned(), value: value.to_owned() }))
+    }
+
+    /// Get the value for a key converted with [`TryFrom<&str>`](TryFrom).
+    ///
+    /// Returns `None` if the key is not present.
+    pub fn get_try_into<'a, T: TryFrom<&'a str>>(&'a self, key: &str) -> Option<Result<T, T::Error>> {
+        self.get(key).map(T::try_from)
+    }
+
+    /// Get the value for a key as a floating point number.
+    ///
+    /// Returns `None` if the key is not present.
+    pub fn get_f64(&self, key: &str, policy: FloatPolicy) -> Option<Result<f64, IniValueError>> {
+        self.value(key).map(|v| v.as_f64().and_then(|f| policy.check(f.is_finite()).map(|_| f)))
+    }
+
+    /// Get the value for a key as a single-precision floating point number.
+    ///
+    /// Returns `None` if the key is not present.
+    pub fn get_f32(&self, key: &str, policy: FloatPolicy) -> Option<Result<f32, IniValueError>> {
+        self.value(key).map(|v| v.as_f32().and_then(|f| policy.check(f.is_finite()).map(|_| f)))
+    }
+
+    /// Get the value for a key as an unsigned integer with an optional unit suffix.
+    ///
+    /// Returns `None` if the key is not present. See [`IniValueRef::as_u64_with_unit`].
+    pub fn get_with_unit(&self, key: &str, units: &[(&str, u64)]) -> Option<Result<u64, IniValueError>> {
+        self.value(key).map(|v| v.as_u64_with_unit(units))
+    }
+
+    /// Replace every key with the result of `f`, such as to make keys lowercase.
+    ///
+    /// Keys are transformed in order of their original name. If multiple keys become the same key, `policy` decides
+    /// whether to keep the value of the last one or return an error, in which case the section is left unchanged.
+    pub fn map_keys<F: FnMut(&str) -> String>(&mut self, mut f: F, policy: DuplicateKeyPolicy) -> Result<(), DuplicateKeyError> {
+        let mut values = BTreeMap::new();
+        for (key, value) in &self.values {
+            let key = f(key);
+            if policy == DuplicateKeyPolicy::Error && values.contains_key(&key) {
+                return Err(DuplicateKeyError { key })
+            }
+            values.insert(key, value.to_owned());
+        }
+        self.values = values;
+        Ok(())
+    }
+
+    /// Returns `true` if both sections have the same keys, and the same values after trimming surrounding whitespace.
+    pub fn eq_trimmed(&self, other: &IniSection) -> bool {
+        self.values.len() == other.values.len()
+            && self.values.iter().zip(&other.values).all(|((key, value), (other_key, other_value))| key == other_key && value.trim() == other_value.trim())
+    }
+
+    /// Get the value of the first key in `keys` that is present, such as to accept both a current and deprecated name.
+    ///
+    /// Returns `None` if none of the keys are present.
+    pub fn get_aliased(&self, keys: &[&str]) -> Option<&str> {
+        keys.iter().find_map(|key| self.get(key))
+    }
+
+    /// Get the values of keys that are unsigned integers, such as `0`, `1`, and `10`, in numeric order.
+    ///
+    /// Keys that are not unsigned integers are skipped.
+    pub fn numeric_keys_sorted(&self) -> impl Iterator<Item = (u64, &str)> {
+        let mut entries: Vec<(u64, &str)> = self.values
+            .iter()
+            .filter_map(|(key, value)| key.parse().ok().map(|index| (index, value.as_str())))
+            .collect();
+        entries.sort_by_key(|(index, _)| *index);
+        entries.into_iter()
+    }
+
+    /// Get the values for several keys at once.
+    ///
+    /// Each element of the returned array is the value for the key at the same position, or `None` if the key is not
+    /// present.
+    pub fn fill<const N: usize>(&self, keys: [&str; N]) -> [Option<&str>; N] {
+        keys.map(|key| self.get(key))
+    }
+
+    /// Set the value for a key.
+    ///
+    /// Returns the previous value, if any.
+    pub fn set(&mut self, key: &str, value: &str) -> Option<String> {
+        self.values.insert(key.to_owned(), value.to_owned())
+    }
+
+    /// Remove a key.
+    ///
+    /// Returns the removed value, or `None` if the key was not present. Keys are kept sorted by name, so the order of
+    /// the remaining keys is unaffected.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.values.remove(key)
+    }
+
+    /// Check whether `key` can be set to `value` such that serializing the section with `mode` and parsing it again
+    /// reads back the same key and value, without changing the section.
+    ///
+    /// For example, a value containing a newline cannot be read back, and neither can a key containing `=`.
+    pub fn can_set(&self, key: &str, value: &str, mode: IniMode) -> Result<(), SetError> {
+        let round_trips = |value: &str| {
+            let mut section = IniSection::default();
+            section.set(key, value);
+            let parsed = Ini::parse(&section.serialize("", mode), mode);
+            parsed.ok().and_then(|ini| ini.get_section("").map(|s| *s == section)) == Some(true)
+        };
+
+        if !round_trips("value") {
+            Err(SetError::InvalidKey { key: key.to_owned() })
+        }
+        else if !round_trips(value) {
+            Err(SetError::InvalidValue { key: key.to_owned(), value: value.to_owned() })
+        }
+        else {
+            Ok(())
+        }
+    }
+
+    /// Iterate over every key and a mutable reference to its value, in order of key.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&str, &mut String)> {
+        self.values.iter_mut().map(|(key, value)| (key.as_str(), value))
+    }
+
+    /// Move the value of `from` to `to`, such as when migrating a renamed key.
+    ///
+    /// The new value of `to` is the result of `combine`, which is given the value of `from` and the existing value of
+    /// `to`, if any. Returns `false` and leaves the section unchanged if `from` is not present.
+    pub fn rename_or_merge_key(&mut self, from: &str, to: &str, combine: impl FnOnce(&str, Option<&str>) -> String) -> bool {
+        let Some(value) = self.values.remove(from) else {
+            return false
+        };
+        let combined = combine(&value, self.get(to));
+        self.values.insert(to.to_owned(), combined);
+        true
+    }
+
+    /// Remove keys with empty or whitespace-only values.
+    pub fn remove_empty(&mut self) {
+        self.values.retain(|_, value| !value.trim().is_empty());
+    }
+
+    /// Get the key at `index`, in order of name.
+    ///
+    /// Returns the key and value, or `None` if `index` is out of bounds. This is O(n).
+    pub fn key_at(&self, index: usize) -> Option<(&str, &str)> {
+        self.values.iter().nth(index).map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Get the value for a key with surrounding whitespace trimmed.
+    ///
+    /// Returns `None` if the key is not present.
+    pub fn get_value_trimmed(&self, key: &str) -> Option<&str> {
+        self.get(key).map(str::trim)
+    }
+
+    /// Copy every key and value into a vector, in order of key.
+    pub fn to_vec(&self) -> Vec<(String, String)> {
+        self.values.iter().map(|(key, value)| (key.to_owned(), value.to_owned())).collect()
+    }
+
+    /// Copy the section with `prefix` prepended to every key.
+    ///
+    /// For example, prefixing `host` with `db.` produces `db.host`.
+    pub fn prefixed(&self, prefix: &str) -> IniSection {
+        IniSection {
+            values: self.values.iter().map(|(key, value)| (alloc::format!("{prefix}{key}"), value.to_owned())).collect()
+        }
+    }
+
+    /// Get the value for a key, wrapped for typed access.
+    ///
+    /// Returns `None` if the key is not present.
+    pub fn value(&self, key: &str) -> Option<IniValueRef<'_>> {
+        self.get(key).map(IniValueRef)
+    }
+
+    /// Parse additional `key=value` lines into the section.
+    ///
+    /// The input follows the same rules as [`Ini::parse`], except it cannot contain section titles. Keys that are
+    /// already present in the section are treated as duplicates. The section is left unchanged if an error occurs.
+    pub fn extend_from_str(&mut self, input: &str, config: IniMode) -> Result<(), IniParsingError> {
+        let mut new_values = BTreeMap::new();
+
+        let options = IniOptions::from(config);
+
+        for (line_number, line) in input.lines().enumerate().map(|(line_index, line)| (line_index + 1, line)) {
+            let (key, value) = match parse_line(line, line_number, &options)? {
+                Line::Blank | Line::Comment => continue,
+                Line::Section(_) => return Err(IniParsingError::UnexpectedSectionTitle { line_number }),
+                Line::KeyValue(key, value) => (key, value)
+            };
+
+            if self.values.contains_key(&key) || new_values.contains_key(&key) {
+                return Err(IniParsingError::DuplicateKey { line_number, key })
+            }
+            new_values.insert(key, value);
+        }
+
+        self.values.append(&mut new_values);
+        Ok(())
+    }
+
+    /// Serialize the section to a string, starting with a `[name]` title.
+    ///
+    /// This is useful for appending a single section to an existing ini file.
+    pub fn serialize(&self, name: &str, mode: IniMode) -> String {
+        self.serialize_with_options(name, &mode.into())
+    }
+
+    /// Serialize the section to a string with additional options, starting with a `[name]` title.
+    ///
+    /// Only [`IniOptions::mode`] and [`IniOptions::newline_escapes`] are applied. Values are written as-is otherwise,
+    /// so output may not read back the same under options such as [`IniOptions::inline_comments`] or
+    /// [`IniOptions::quoted_values`].
+    pub fn serialize_with_options(&self, name: &str, options: &IniOptions) -> String {
+        let mut output = String::new();
+        self.serialize_into(name, options, &mut output);
+        output
+    }
+
+    fn serialize_into(&self, name: &str, options: &IniOptions, output: &mut String) {
+        output.push('[');
+        output.push_str(name);
+        output.push_str("]\n");
+
+        let separator = match options.mode {
+            IniMode::Simple => "=",
+            IniMode::SimpleTrimmed => " = "
+        };
+
+        for (key, value) in &self.values {
+            output.push_str(key);
+            output.push_str(separator);
+            if options.newline_escapes {
+                output.push_str(&value.replace('\n', "\\n"));
+            }
+            else {
+                output.push_str(value);
+            }
+            output.push('\n');
+        }
+    }
+}
+
+/// An error generated by the ini parser.
+///
+/// Errors are ordered by line number, so collected errors can be sorted from the top of the file to the bottom.
+#[derive(Clone, PartialEq, Eq)]
+pub enum IniParsingError {
+    MissingEquals { line_number: usize },
+    ExpectedSectionTitle { line_number: usize },
+    BrokenSectionTitle { line_number: usize },
+    DuplicateSection { line_number: usize, section: String },
+    DuplicateSectionKey { line_number: usize, section: String, key: String },
+    UnexpectedSectionTitle { line_number: usize },
+    DuplicateKey { line_number: usize, key: String },
+    TabNotAllowed { line_number: usize },
+    TabInValue { line_number: usize },
+    NonCanonicalWhitespace { line_number: usize },
+    TrailingEmptySection { line_number: usize, section: String },
+    LimitExceeded { line_number: usize, section: String, key: String },
+    UnexpectedContinuation { line_number: usize },
+    DanglingContinuation { line_number: usize },
+}
+
+impl IniParsingError {
+    /// Get the line number the error occurred on.
+    pub fn line_number(&self) -> usize {
+        self.sort_key().0
+    }
+
+    /// Get the error message as a string, as written by [`Display`].
+    pub fn to_message(&self) -> String {
+        self.to_string()
+    }
+
+    fn sort_key(&self) -> (usize, u8, &str, &str) {
+        match self {
+            Self::MissingEquals { line_number } => (*line_number, 0, "", ""),
+            Self::ExpectedSectionTitle { line_number } => (*line_number, 1, "", ""),
+            Self::BrokenSectionTitle { line_number } => (*line_number, 2, "", ""),
+            Self::DuplicateSection { line_number, section } => (*line_number, 3, section, ""),
+            Self::DuplicateSectionKey { line_number, section, key } => (*line_number, 4, section, key),
+            Self::UnexpectedSectionTitle { line_number } => (*line_number, 5, "", ""),
+            Self::DuplicateKey { line_number, key } => (*line_number, 6, "", key),
+            Self::TabNotAllowed { line_number } => (*line_number, 7, "", ""),
+            Self::TabInValue { line_number } => (*line_number, 8, "", ""),
+            Self::NonCanonicalWhitespace { line_number } => (*line_number, 9, "", ""),
+            Self::TrailingEmptySection { line_number, section } => (*line_number, 10, section, ""),
+            Self::LimitExceeded { line_number, section, key } => (*line_number, 11, section, key),
+            Self::UnexpectedContinuation { line_number } => (*line_number, 12, "", ""),
+            Self::DanglingContinuation { line_number } => (*line_number, 13, "", "")
+        }
+    }
+}
+
+impl PartialOrd for IniParsingError {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for IniParsingError {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+impl Display for IniParsingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingEquals { line_number } => f.write_fmt(format_args!("Parsing error on line {line_number}: Missing an `=` to separate the key and value")),
+            Self::ExpectedSectionTitle { line_number } => f.write_fmt(format_args!("Parsing error on line {line_number}: Expected a section title")),
+            Self::BrokenSectionTitle { line_number } => f.write_fmt(format_args!("Parsing error on line {line_number}: Expected a `]` to close a `[`")),
+            Self::DuplicateSection { line_number, section } => f.write_fmt(format_args!("Parsing error on line {line_number}: Duplicate section `{section}`")),
+            Self::DuplicateSectionKey { line_number, section, key } => f.write_fmt(format_args!("Parsing error on line {line_number}: Duplicate key `{key}` in section `{section}`")),
+            Self::UnexpectedSectionTitle { line_number } => f.write_fmt(format_args!("Parsing error on line {line_number}: Unexpected section title")),
+            Self::DuplicateKey { line_number, key } => f.write_fmt(format_args!("Parsing error on line {line_number}: Duplicate key `{key}`")),
+            Self::TabNotAllowed { line_number } => f.write_fmt(format_args!("Parsing error on line {line_number}: Tab characters are not allowed")),
+            Self::TabInValue { line_number } => f.write_fmt(format_args!("Parsing error on line {line_number}: Tab characters are not allowed in values")),
+            Self::NonCanonicalWhitespace { line_number } => f.write_fmt(format_args!("Parsing error on line {line_number}: Keys and values cannot start or end with whitespace")),
+            Self::TrailingEmptySection { line_number, section } => f.write_fmt(format_args!("Parsing error on line {line_number}: Section `{section}` at the end of the file has no keys")),
+            Self::LimitExceeded { line_number, section, key } => f.write_fmt(format_args!("Parsing error on line {line_number}: Key `{key}` in section `{section}` is repeated too many times")),
+            Self::UnexpectedContinuation { line_number } => f.write_fmt(format_args!("Parsing error on line {line_number}: Continuation line does not follow a key")),
+            Self::DanglingContinuation { line_number } => f.write_fmt(format_args!("Parsing error on line {line_number}: Expected a line to continue onto after `\\`"))
+        }
+    }
+}
+
+impl Debug for IniParsingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl TryFrom<&[u8]> for Ini {
+    type Error = IniBytesError;
+
+    /// Parse UTF-8 encoded bytes with the default [`IniMode`].
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let string = core::str::from_utf8(bytes).map_err(IniBytesError::Utf8)?;
+        Ini::parse(string, IniMode::default()).map_err(IniBytesError::Parse)
+    }
+}
+
+/// An error generated when parsing an ini from bytes.
+#[derive(Clone, PartialEq)]
+pub enum IniBytesError {
+    Utf8(Utf8Error),
+    Parse(IniParsingError),
+}
+
+impl Display for IniBytesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Utf8(error) => f.write_fmt(format_args!("Encoding error: {error}")),
+            Self::Parse(error) => Display::fmt(error, f)
+        }
+    }
+}
+
+impl Debug for IniBytesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// A warning generated by the ini parser.
+#[derive(Clone, PartialEq)]
+pub enum IniWarning {
+    PlaceholderValue { line_number: usize, key: String },
+    TrailingEmptySection { line_number: usize, section: String },
+}
+
+impl Display for IniWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::PlaceholderValue { line_number, key } => f.write_fmt(format_args!("Warning on line {line_number}: Key `{key}` has a placeholder value")),
+            Self::TrailingEmptySection { line_number, section } => f.write_fmt(format_args!("Warning on line {line_number}: Section `{section}` at the end of the file has no keys"))
+        }
+    }
+}
+
+impl Debug for IniWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// An error generated when an edit would produce a duplicate key in a section.
+#[derive(Clone, PartialEq)]
+pub struct DuplicateKeyError {
+    pub key: String
+}
+
+impl Display for DuplicateKeyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("Edit error: Duplicate key `{}`", self.key))
+    }
+}
+
+impl Debug for DuplicateKeyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// An error generated when a key or value cannot be written and read back, as reported by [`IniSection::can_set`].
+#[derive(Clone, PartialEq)]
+pub enum SetError {
+    InvalidKey { key: String },
+    InvalidValue { key: String, value: String },
+}
+
+impl Display for SetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidKey { key } => f.write_fmt(format_args!("Edit error: Key `{key}` cannot be serialized and read back")),
+            Self::InvalidValue { key, value } => f.write_fmt(format_args!("Edit error: Value `{value}` for key `{key}` cannot be serialized and read back"))
+        }
+    }
+}
+
+impl Debug for SetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// A section or key that is not allowed, as reported by [`Ini::validate_keys`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum UnknownKey {
+    Section { section: String },
+    Key { section: String, key: String },
+}
+
+/// An error generated when applying an override with [`Ini::apply_override`].
+#[derive(Clone, PartialEq)]
+pub enum OverrideError {
+    MissingEquals { spec: String },
+    MissingSection { spec: String },
+}
+
+impl Display for OverrideError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingEquals { spec } => f.write_fmt(format_args!("Override error in `{spec}`: Missing an `=` to separate the key and value")),
+            Self::MissingSection { spec } => f.write_fmt(format_args!("Override error in `{spec}`: Missing a `.` to separate the section and key"))
+        }
+    }
+}
+
+impl Debug for OverrideError {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         Display::fmt(self, f)
     }
@@ -0,0 +1,119 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter};
+use crate::ini::{Ini, IniSection};
+
+/// Builder for constructing an [`Ini`] programmatically.
+///
+/// ```rust
+/// use gerbil_ini::IniBuilder;
+///
+/// let ini = IniBuilder::new()
+///     .section("Window")
+///         .key("width", "640")
+///         .key("height", "480")
+///     .section("Audio")
+///         .key("volume", "11")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(ini.get_value("Audio", "volume"), Some("11"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct IniBuilder {
+    sections: Vec<(String, Vec<(String, String)>)>
+}
+
+/// Builder for a single section of an [`IniBuilder`].
+#[derive(Clone, Debug)]
+pub struct IniSectionBuilder {
+    parent: IniBuilder,
+    name: String,
+    values: Vec<(String, String)>
+}
+
+impl IniBuilder {
+    /// Create an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a new section.
+    pub fn section(self, name: &str) -> IniSectionBuilder {
+        IniSectionBuilder { parent: self, name: name.to_owned(), values: Vec::new() }
+    }
+
+    /// Build the ini.
+    ///
+    /// Returns an error if a section or a key within a section is specified more than once.
+    pub fn build(self) -> Result<Ini, IniBuildError> {
+        let mut ini = Ini::default();
+
+        for (name, values) in self.sections {
+            if ini.sections.contains_key(&name) {
+                return Err(IniBuildError::DuplicateSection { section: name })
+            }
+
+            let mut section = IniSection::default();
+            for (key, value) in values {
+                if section.values.contains_key(&key) {
+                    return Err(IniBuildError::DuplicateSectionKey { section: name, key })
+                }
+                section.values.insert(key, value);
+            }
+
+            *ini.section_or_insert(&name) = section;
+        }
+
+        Ok(ini)
+    }
+}
+
+impl IniSectionBuilder {
+    /// Add a key to the section.
+    pub fn key(mut self, key: &str, value: &str) -> Self {
+        self.values.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Finish this section and start a new one.
+    pub fn section(self, name: &str) -> IniSectionBuilder {
+        self.finish().section(name)
+    }
+
+    /// Finish this section, returning to the parent builder.
+    pub fn finish(mut self) -> IniBuilder {
+        self.parent.sections.push((self.name, self.values));
+        self.parent
+    }
+
+    /// Finish this section and build the ini.
+    ///
+    /// See [`IniBuilder::build`].
+    pub fn build(self) -> Result<Ini, IniBuildError> {
+        self.finish().build()
+    }
+}
+
+/// An error generated when building an ini with [`IniBuilder`].
+#[derive(Clone, PartialEq)]
+pub enum IniBuildError {
+    DuplicateSection { section: String },
+    DuplicateSectionKey { section: String, key: String },
+}
+
+impl Display for IniBuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::DuplicateSection { section } => f.write_fmt(format_args!("Build error: Duplicate section `{section}`")),
+            Self::DuplicateSectionKey { section, key } => f.write_fmt(format_args!("Build error: Duplicate key `{key}` in section `{section}`"))
+        }
+    }
+}
+
+impl Debug for IniBuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
@@ -0,0 +1,158 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use crate::ini::{Ini, IniSection};
+
+/// Differences between two sections, as produced by [`IniSection::diff`].
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct SectionDiff {
+    /// Keys only present in the other section, with their values.
+    pub added: BTreeMap<String, String>,
+
+    /// Keys only present in this section, with their values.
+    pub removed: BTreeMap<String, String>,
+
+    /// Keys present in both sections with different values, with the old and new values.
+    pub changed: BTreeMap<String, (String, String)>
+}
+
+impl SectionDiff {
+    /// Returns `true` if there are no differences.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Differences between two inis, as produced by [`Ini::diff`].
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct IniDiff {
+    /// Sections only present in the other ini.
+    pub added: BTreeMap<String, IniSection>,
+
+    /// Sections only present in this ini.
+    pub removed: BTreeMap<String, IniSection>,
+
+    /// Sections present in both inis with different contents.
+    pub changed: BTreeMap<String, SectionDiff>
+}
+
+impl IniDiff {
+    /// Returns `true` if there are no differences.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Write the differences in an ini-like format, with sections in order of name.
+    ///
+    /// Within each section, `+key=value` sets a key and `-key` removes it. Removed sections are written as `-[name]`
+    /// with no keys.
+    pub fn to_ini_patch(&self) -> String {
+        let mut sections: BTreeMap<&str, String> = BTreeMap::new();
+
+        for name in self.removed.keys() {
+            sections.insert(name, alloc::format!("-[{name}]\n"));
+        }
+
+        for (name, section) in &self.added {
+            let mut patch = alloc::format!("[{name}]\n");
+            for (key, value) in &section.values {
+                patch += &alloc::format!("+{key}={value}\n");
+            }
+            sections.insert(name, patch);
+        }
+
+        for (name, diff) in &self.changed {
+            let mut lines: BTreeMap<&str, String> = BTreeMap::new();
+            for key in diff.removed.keys() {
+                lines.insert(key, alloc::format!("-{key}\n"));
+            }
+            for (key, value) in diff.added.iter().chain(diff.changed.iter().map(|(key, (_, new))| (key, new))) {
+                lines.insert(key, alloc::format!("+{key}={value}\n"));
+            }
+
+            let mut patch = alloc::format!("[{name}]\n");
+            lines.values().for_each(|line| patch += line);
+            sections.insert(name, patch);
+        }
+
+        let mut patch = String::new();
+        for (index, section) in sections.values().enumerate() {
+            if index != 0 {
+                patch.push('\n');
+            }
+            patch += section;
+        }
+        patch
+    }
+}
+
+impl Ini {
+    /// Get the differences between this ini and `other`.
+    ///
+    /// Sections that are only in `other` are added, and sections that are only in this ini are removed.
+    pub fn diff(&self, other: &Ini) -> IniDiff {
+        let mut diff = IniDiff::default();
+
+        for (name, section) in &self.sections {
+            match other.sections.get(name) {
+                None => { diff.removed.insert(name.to_owned(), section.clone()); },
+                Some(new_section) => {
+                    let section_diff = section.diff(new_section);
+                    if !section_diff.is_empty() {
+                        diff.changed.insert(name.to_owned(), section_diff);
+                    }
+                }
+            }
+        }
+
+        for (name, section) in &other.sections {
+            if !self.sections.contains_key(name) {
+                diff.added.insert(name.to_owned(), section.clone());
+            }
+        }
+
+        diff
+    }
+}
+
+impl IniSection {
+    /// Get the differences between this section and `other`.
+    ///
+    /// Keys that are only in `other` are added, and keys that are only in this section are removed.
+    pub fn diff(&self, other: &IniSection) -> SectionDiff {
+        let mut diff = SectionDiff::default();
+
+        for (key, value) in &self.values {
+            match other.values.get(key) {
+                None => { diff.removed.insert(key.to_owned(), value.to_owned()); },
+                Some(new_value) if new_value != value => { diff.changed.insert(key.to_owned(), (value.to_owned(), new_value.to_owned())); },
+                Some(_) => ()
+            }
+        }
+
+        for (key, value) in &other.values {
+            if !self.values.contains_key(key) {
+                diff.added.insert(key.to_owned(), value.to_owned());
+            }
+        }
+
+        diff
+    }
+
+    /// Returns `true` if every key in this section is also in `other` with the same value.
+    pub fn is_subset_of(&self, other: &IniSection) -> bool {
+        self.values.iter().all(|(key, value)| other.values.get(key) == Some(value))
+    }
+
+    /// Get the keys in this section that are missing from `other` or have a different value in it.
+    ///
+    /// This is empty if and only if the section is a subset of `other`.
+    pub fn difference(&self, other: &IniSection) -> Vec<&str> {
+        self.values
+            .iter()
+            .filter(|(key, value)| other.values.get(*key) != Some(*value))
+            .map(|(key, _)| key.as_str())
+            .collect()
+    }
+}
@@ -0,0 +1,62 @@
+use alloc::string::String;
+use crate::ini::{parse_line, Ini, IniMode, IniOptions, IniParsingError, Line, LogicalLine, LogicalLines};
+
+/// A section title or key found while reading an ini with [`Ini::entries`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum IniEvent {
+    Section { line_number: usize, name: String },
+    KeyValue { line_number: usize, key: String, value: String },
+}
+
+/// Iterator over the section titles and keys of an ini, in the order they appear.
+///
+/// Comments and blank lines are skipped. Each line is checked on its own, so errors that depend on other lines, such
+/// as duplicate sections or keys, are not detected. Iteration ends after the first error.
+#[derive(Clone, Debug)]
+pub struct IniEntries<'a> {
+    lines: LogicalLines<'a>,
+    options: IniOptions<'a>,
+    finished: bool
+}
+
+impl Ini {
+    /// Read the entries of an ini lazily, without building an [`Ini`].
+    pub fn entries(string: &str, config: IniMode) -> IniEntries<'_> {
+        Self::entries_with_options(string, config.into())
+    }
+
+    /// Read the entries of an ini lazily with additional options, without building an [`Ini`].
+    pub fn entries_with_options<'a>(string: &'a str, options: IniOptions<'a>) -> IniEntries<'a> {
+        IniEntries { lines: LogicalLines::new(string, options), options, finished: false }
+    }
+}
+
+impl Iterator for IniEntries<'_> {
+    type Item = Result<IniEvent, IniParsingError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None
+        }
+
+        for logical_line in self.lines.by_ref() {
+            let parsed = logical_line.and_then(|LogicalLine { line_number, text, .. }| {
+                parse_line(&text, line_number, &self.options).map(|line| (line_number, line))
+            });
+            let event = match parsed {
+                Ok((_, Line::Blank | Line::Comment)) => continue,
+                Ok((_, Line::Section(name))) if self.options.stop_at_section == Some(name.as_str()) => break,
+                Ok((line_number, Line::Section(name))) => Ok(IniEvent::Section { line_number, name }),
+                Ok((line_number, Line::KeyValue(key, value))) => Ok(IniEvent::KeyValue { line_number, key, value }),
+                Err(e) => {
+                    self.finished = true;
+                    Err(e)
+                }
+            };
+            return Some(event)
+        }
+
+        self.finished = true;
+        None
+    }
+}
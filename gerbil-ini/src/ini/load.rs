@@ -0,0 +1,17 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use crate::ini::{Ini, IniSection, IniValueError};
+
+/// A type that can be read from a section, for use with [`Ini::load_all`].
+pub trait FromIniSection: Sized {
+    /// Read the type from the section.
+    fn from_section(section: &IniSection) -> Result<Self, IniValueError>;
+}
+
+impl Ini {
+    /// Read every section as `T`, returning the result for each section by name.
+    pub fn load_all<T: FromIniSection>(&self) -> BTreeMap<String, Result<T, IniValueError>> {
+        self.sections.iter().map(|(name, section)| (name.to_owned(), T::from_section(section))).collect()
+    }
+}
@@ -0,0 +1,51 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter};
+use std::net::{AddrParseError, SocketAddr};
+use crate::ini::IniSection;
+
+impl IniSection {
+    /// Get the value for a key as a list of socket addresses separated by `delimiter`, such as
+    /// `1.2.3.4:80, 5.6.7.8:80`.
+    ///
+    /// Whitespace around each address is ignored. Returns `None` if the key is not present.
+    pub fn get_socket_addrs(&self, key: &str, delimiter: char) -> Option<Result<Vec<SocketAddr>, SocketAddrListError>> {
+        self.get(key).map(|value| {
+            value
+                .split(delimiter)
+                .enumerate()
+                .map(|(index, element)| {
+                    let element = element.trim();
+                    element.parse().map_err(|error| SocketAddrListError { index, element: element.to_owned(), error })
+                })
+                .collect()
+        })
+    }
+}
+
+/// An element of a list of socket addresses could not be parsed.
+#[derive(Clone, PartialEq)]
+pub struct SocketAddrListError {
+    /// Index of the element in the list, starting from 0.
+    pub index: usize,
+
+    /// The element, with surrounding whitespace removed.
+    pub element: String,
+
+    /// The error from parsing the element.
+    pub error: AddrParseError
+}
+
+impl Display for SocketAddrListError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let Self { index, element, error } = self;
+        f.write_fmt(format_args!("Value error: Element {index} (`{element}`) is not a valid socket address: {error}"))
+    }
+}
+
+impl Debug for SocketAddrListError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
@@ -0,0 +1,82 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter};
+use crate::ini::{parse_line, Ini, IniOptions, IniParsingError, Line};
+
+impl Ini {
+    /// Update the ini after a single line of its source was edited, without reparsing the whole file.
+    ///
+    /// `old_line` and `new_line` are the contents of the line at `line_number` before and after the edit, and `options`
+    /// must be the same options the ini was parsed with.
+    ///
+    /// The ini does not keep track of which line each key came from, so the owning section of an edited key is found
+    /// by searching for its old key and value. This has some limitations, which return
+    /// [`ReparseError::FullReparseRequired`] and leave the ini unchanged:
+    /// * Edits that add, remove, or change a section title
+    /// * Edits that turn a comment or blank line into a key, since the section it belongs to is unknown
+    /// * Edits to a key whose old key and value appear in more than one section
+    pub fn reparse_line(&mut self, line_number: usize, old_line: &str, new_line: &str, options: &IniOptions) -> Result<(), ReparseError> {
+        let full_reparse = ReparseError::FullReparseRequired { line_number };
+        let old = parse_line(old_line, line_number, options).map_err(|_| full_reparse.clone())?;
+        let new = parse_line(new_line, line_number, options)?;
+
+        let (old_key, old_value) = match (old, &new) {
+            (Line::Blank | Line::Comment, Line::Blank | Line::Comment) => return Ok(()),
+            (Line::Section(_), _) | (_, Line::Section(_)) => return Err(full_reparse),
+            (Line::Blank | Line::Comment, Line::KeyValue(..)) => return Err(full_reparse),
+            (Line::KeyValue(key, value), _) => (key, value)
+        };
+
+        let candidates: Vec<&String> = self.sections
+            .iter()
+            .filter(|(_, section)| section.get(&old_key) == Some(old_value.as_str()))
+            .map(|(name, _)| name)
+            .collect();
+        let [section_name] = candidates.as_slice() else {
+            return Err(full_reparse)
+        };
+        let section_name = section_name.to_string();
+        let section = self.sections.get_mut(&section_name).unwrap();
+
+        if let Line::KeyValue(key, _) = &new {
+            if *key != old_key && section.values.contains_key(key) {
+                return Err(IniParsingError::DuplicateSectionKey { line_number, section: section_name, key: key.to_string() }.into())
+            }
+        }
+
+        section.values.remove(&old_key);
+        if let Line::KeyValue(key, value) = new {
+            section.values.insert(key, value);
+        }
+
+        Ok(())
+    }
+}
+
+/// An error generated by [`Ini::reparse_line`].
+#[derive(Clone, PartialEq)]
+pub enum ReparseError {
+    Parse(IniParsingError),
+    FullReparseRequired { line_number: usize },
+}
+
+impl From<IniParsingError> for ReparseError {
+    fn from(error: IniParsingError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl Display for ReparseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Parse(error) => Display::fmt(error, f),
+            Self::FullReparseRequired { line_number } => f.write_fmt(format_args!("Reparse error on line {line_number}: The edit requires reparsing the whole file"))
+        }
+    }
+}
+
+impl Debug for ReparseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
@@ -0,0 +1,49 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use crate::ini::Ini;
+
+impl Ini {
+    /// Get the value in the section of the ini, expanding references to other values.
+    ///
+    /// `${key}` refers to a key in the same section, and `${section:key}` refers to a key in another section.
+    /// The section name is everything before the last `:`, so section names may contain `:` but referenced keys cannot.
+    /// References are expanded recursively when read, and the stored values are left unchanged. An unterminated
+    /// `${` is kept as-is.
+    ///
+    /// Returns `None` if the section or key do not exist, if a reference refers to a missing value, or if references
+    /// form a cycle.
+    pub fn resolve(&self, section: &str, key: &str) -> Option<Cow<'_, str>> {
+        self.resolve_inner(section, key, &mut Vec::new())
+    }
+
+    fn resolve_inner<'a: 'b, 'b>(&'a self, section: &'b str, key: &'b str, visiting: &mut Vec<(&'b str, &'b str)>) -> Option<Cow<'a, str>> {
+        let value = self.get_value(section, key)?;
+        if !value.contains("${") {
+            return Some(Cow::Borrowed(value))
+        }
+
+        if visiting.contains(&(section, key)) {
+            return None
+        }
+        visiting.push((section, key));
+
+        let mut resolved = String::with_capacity(value.len());
+        let mut remaining = value;
+        while let Some(start) = remaining.find("${") {
+            let Some(end) = remaining[start..].find('}') else {
+                break
+            };
+            let reference = &remaining[start + 2..start + end];
+            let (reference_section, reference_key) = reference.rsplit_once(':').unwrap_or((section, reference));
+
+            resolved.push_str(&remaining[..start]);
+            resolved.push_str(&self.resolve_inner(reference_section, reference_key, visiting)?);
+            remaining = &remaining[start + end + 1..];
+        }
+        resolved.push_str(remaining);
+
+        visiting.pop();
+        Some(Cow::Owned(resolved))
+    }
+}
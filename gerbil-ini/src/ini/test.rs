@@ -1,6 +1,8 @@
-use alloc::borrow::ToOwned;
+use alloc::borrow::{Cow, ToOwned};
 use alloc::collections::BTreeMap;
-use crate::ini::{Ini, IniMode, IniSection};
+use alloc::vec;
+use alloc::vec::Vec;
+use crate::ini::{DanglingContinuationPolicy, DuplicateKeyError, DuplicateKeyPolicy, DuplicateSectionPolicy, EmptySectionPolicy, FloatPolicy, FromIniSection, Ini, IniBuildError, IniBuilder, IniBytesError, IniEvent, IniMode, IniOptions, IniParsingError, IniSection, IniValueError, IniWarning, OverrideError, ParseFieldError, ReparseError, SetError, UnknownKey, ValueProcessingStep};
 
 const SIMPLE_TEST_INI: &str = r#"
 ; This is a comment.
@@ -72,8 +74,10 @@ fn simple_ini_parse_test() {
             });
 
             sections
-        }
+        },
+        section_order: vec!["My Section".to_owned(), "Another Section".to_owned()]
     }, ini);
+    assert_eq!(ini.section_names_in_order().collect::<Vec<_>>(), ini.section_order);
 }
 
 #[test]
@@ -83,3 +87,1282 @@ fn simple_ini_parse_trimmed_test() {
     assert_eq!(ini, ini_trimmed);
 }
 
+#[test]
+fn serialize_round_trip_test() {
+    let ini = Ini::parse(SIMPLE_TEST_INI, IniMode::Simple).unwrap();
+    assert_eq!(ini, Ini::parse(&ini.serialize(IniMode::Simple), IniMode::Simple).unwrap());
+
+    let ini_trimmed = Ini::parse(SIMPLE_TRIMMED_TEST_INI, IniMode::SimpleTrimmed).unwrap();
+    assert_eq!(ini_trimmed, Ini::parse(&ini_trimmed.serialize(IniMode::SimpleTrimmed), IniMode::SimpleTrimmed).unwrap());
+}
+
+#[test]
+fn serialize_section_test() {
+    let ini = Ini::parse("[My Section]\nkey=value\nanother key=another value\n", IniMode::Simple).unwrap();
+    let section = ini.get_section("My Section").unwrap();
+
+    for mode in [IniMode::Simple, IniMode::SimpleTrimmed] {
+        assert_eq!(section.serialize("My Section", mode), ini.serialize(mode));
+    }
+    assert_eq!(section.serialize("My Section", IniMode::Simple), "[My Section]\nanother key=another value\nkey=value\n");
+}
+
+#[test]
+fn section_extend_from_str_test() {
+    let mut ini = Ini::parse(SIMPLE_TRIMMED_TEST_INI, IniMode::SimpleTrimmed).unwrap();
+    let section = ini.sections.get_mut("My Section").unwrap();
+
+    section.extend_from_str("; computed\nwidth = 640\nheight = 480\n", IniMode::SimpleTrimmed).unwrap();
+    assert_eq!(section.get("width"), Some("640"));
+    assert_eq!(section.get("height"), Some("480"));
+    assert_eq!(section.get("some KEY"), Some("This is a value!"));
+
+    assert_eq!(section.extend_from_str("depth = 24\nwidth = 800", IniMode::SimpleTrimmed), Err(IniParsingError::DuplicateKey { line_number: 2, key: "width".to_owned() }));
+    assert_eq!(section.get("depth"), None);
+    assert_eq!(section.extend_from_str("[Section]", IniMode::SimpleTrimmed), Err(IniParsingError::UnexpectedSectionTitle { line_number: 1 }));
+}
+
+#[test]
+fn value_ref_test() {
+    let ini = Ini::parse("[Values]\nenabled=Yes\ndisabled=off\ncount=-42\nratio=0.5\nname=gerbil\n", IniMode::Simple).unwrap();
+    let section = ini.get_section("Values").unwrap();
+
+    assert_eq!(section.value("enabled").unwrap().as_bool(), Ok(true));
+    assert_eq!(section.value("disabled").unwrap().as_bool(), Ok(false));
+    assert_eq!(section.value("name").unwrap().as_bool(), Err(IniValueError::Invalid));
+
+    assert_eq!(section.value("count").unwrap().as_i64(), Ok(-42));
+    assert_eq!(section.value("ratio").unwrap().as_i64(), Err(IniValueError::Invalid));
+
+    assert_eq!(section.value("ratio").unwrap().as_f64(), Ok(0.5));
+    assert_eq!(section.value("count").unwrap().as_f64(), Ok(-42.0));
+    assert_eq!(section.value("name").unwrap().as_f64(), Err(IniValueError::Invalid));
+
+    assert_eq!(section.value("name").unwrap().as_str(), "gerbil");
+    assert_eq!(section.value("missing"), None);
+}
+
+#[test]
+fn section_title_with_equals_test() {
+    let ini = Ini::parse("[a=b]\nkey=value\n[key=val group]\n", IniMode::Simple).unwrap();
+    assert_eq!(ini.get_value("a=b", "key"), Some("value"));
+    assert!(ini.get_section("key=val group").is_some());
+    assert!(ini.get_section("a").is_none());
+}
+
+#[test]
+fn forbid_tabs_test() {
+    let options = IniOptions { forbid_tabs: true, ..IniMode::SimpleTrimmed.into() };
+    assert_eq!(Ini::parse_with_options(SIMPLE_TRIMMED_TEST_INI, &options), Ini::parse(SIMPLE_TRIMMED_TEST_INI, IniMode::SimpleTrimmed));
+
+    let tabbed = "[Section]\nkey =\tvalue\n";
+    assert!(Ini::parse(tabbed, IniMode::SimpleTrimmed).is_ok());
+    assert_eq!(Ini::parse_with_options(tabbed, &options), Err(IniParsingError::TabNotAllowed { line_number: 2 }));
+    assert_eq!(Ini::parse_with_options("[Section]\n;\tcomment\n", &options), Err(IniParsingError::TabNotAllowed { line_number: 2 }));
+}
+
+#[test]
+fn apply_override_test() {
+    let mut ini = Ini::parse(SIMPLE_TEST_INI, IniMode::Simple).unwrap();
+
+    ini.apply_override("My Section.anotherkey=overridden").unwrap();
+    assert_eq!(ini.get_value("My Section", "anotherkey"), Some("overridden"));
+
+    ini.apply_overrides(["plugin.audio.volume=11", "plugin.audio.muted=false"]).unwrap();
+    assert_eq!(ini.get_value("plugin.audio", "volume"), Some("11"));
+    assert_eq!(ini.get_value("plugin.audio", "muted"), Some("false"));
+
+    assert_eq!(ini.apply_override("My Section.anotherkey"), Err(OverrideError::MissingEquals { spec: "My Section.anotherkey".to_owned() }));
+    assert_eq!(ini.apply_override("anotherkey=value"), Err(OverrideError::MissingSection { spec: "anotherkey=value".to_owned() }));
+    assert_eq!(ini.get_value("My Section", "anotherkey"), Some("overridden"));
+}
+
+#[test]
+fn builder_test() {
+    let ini = IniBuilder::new()
+        .section("My Section")
+            .key("some KEY", "This is a value!")
+            .key("anotherkey", "This is yet another value!")
+        .section("Another Section")
+            .key("yourkey", "This is a value!")
+            .key("some KEY", "This, too, is a value!")
+            .key("anotherkey", r#"//Wow Look At Me I'm A Value\\"#)
+        .build()
+        .unwrap();
+    assert_eq!(ini, Ini::parse(SIMPLE_TEST_INI, IniMode::Simple).unwrap());
+
+    assert_eq!(IniBuilder::new().section("a").finish().section("a").build(), Err(IniBuildError::DuplicateSection { section: "a".to_owned() }));
+    assert_eq!(IniBuilder::new().section("a").key("k", "1").key("k", "2").build(), Err(IniBuildError::DuplicateSectionKey { section: "a".to_owned(), key: "k".to_owned() }));
+}
+
+#[test]
+fn forbid_value_tabs_test() {
+    let options = IniOptions { forbid_value_tabs: true, ..Default::default() };
+    let tabbed = "[Section]\nkey\t=value\nother=tabbed\tvalue\n";
+    assert!(Ini::parse(tabbed, IniMode::Simple).is_ok());
+    assert_eq!(Ini::parse_with_options(tabbed, &options), Err(IniParsingError::TabInValue { line_number: 3 }));
+    assert!(Ini::parse_with_options("[Section]\nkey\t=value\n", &options).is_ok());
+}
+
+#[test]
+fn collapse_key_whitespace_test() {
+    let options = IniOptions { collapse_key_whitespace: true, ..Default::default() };
+    let ini = Ini::parse_with_options("[Section]\nsome   KEY=value\nother \t key=other value\n", &options).unwrap();
+    assert_eq!(ini.get_value("Section", "some KEY"), Some("value"));
+    assert_eq!(ini.get_value("Section", "other key"), Some("other value"));
+    assert_eq!(ini.get_value("Section", "some   KEY"), None);
+
+    let duplicated = "[Section]\nsome KEY=value\nsome   KEY=value\n";
+    assert!(Ini::parse(duplicated, IniMode::Simple).is_ok());
+    assert_eq!(Ini::parse_with_options(duplicated, &options), Err(IniParsingError::DuplicateSectionKey { line_number: 3, section: "Section".to_owned(), key: "some KEY".to_owned() }));
+}
+
+#[test]
+fn get_or_test() {
+    let ini = Ini::parse("[Window]\nwidth=640\nheight=tall\n", IniMode::Simple).unwrap();
+    assert_eq!(ini.get_or("Window", "width", 320u32), 640);
+    assert_eq!(ini.get_or("Window", "height", 240u32), 240);
+    assert_eq!(ini.get_or("Window", "depth", 24u32), 24);
+    assert_eq!(ini.get_or("Audio", "width", 0u32), 0);
+}
+
+#[test]
+fn section_fill_test() {
+    let ini = Ini::parse(SIMPLE_TEST_INI, IniMode::Simple).unwrap();
+    let section = ini.get_section("Another Section").unwrap();
+    assert_eq!(section.fill(["yourkey", "missing", "some KEY"]), [Some("This is a value!"), None, Some("This, too, is a value!")]);
+}
+
+#[test]
+fn max_section_depth_test() {
+    assert_eq!(Ini::default().max_section_depth(), 0);
+    assert_eq!(Ini::parse("[a]\n[a.b]\n[a.b.c]\n[d]\n", IniMode::Simple).unwrap().max_section_depth(), 3);
+}
+
+#[test]
+fn get_with_unit_test() {
+    const UNITS: &[(&str, u64)] = &[("k", 1000), ("rpm", 1), ("M", 1000000)];
+
+    let ini = Ini::parse("[Limits]\nrequests=5k\nspeed=300 rpm\nplain=42\nunknown=5q\nbad=k\nhuge=99999999999999M\n", IniMode::Simple).unwrap();
+    let section = ini.get_section("Limits").unwrap();
+    assert_eq!(section.get_with_unit("requests", UNITS), Some(Ok(5000)));
+    assert_eq!(section.get_with_unit("speed", UNITS), Some(Ok(300)));
+    assert_eq!(section.get_with_unit("plain", UNITS), Some(Ok(42)));
+    assert_eq!(section.get_with_unit("unknown", UNITS), Some(Err(IniValueError::UnknownUnit)));
+    assert_eq!(section.get_with_unit("bad", UNITS), Some(Err(IniValueError::Invalid)));
+    assert_eq!(section.get_with_unit("huge", UNITS), Some(Err(IniValueError::Invalid)));
+    assert_eq!(section.get_with_unit("missing", UNITS), None);
+}
+
+#[test]
+fn newline_escapes_test() {
+    let options = IniOptions { newline_escapes: true, ..Default::default() };
+    let escaped = "[Message]\ntext=first line\\nsecond line\n";
+
+    let ini = Ini::parse_with_options(escaped, &options).unwrap();
+    assert_eq!(ini.get_value("Message", "text"), Some("first line\nsecond line"));
+    assert_eq!(ini.serialize_with_options(&options), escaped);
+
+    let literal = Ini::parse(escaped, IniMode::Simple).unwrap();
+    assert_eq!(literal.get_value("Message", "text"), Some("first line\\nsecond line"));
+    assert_eq!(literal.serialize(IniMode::Simple), escaped);
+
+    let ini = Ini::parse_with_options(SIMPLE_TEST_INI, &options).unwrap();
+    assert_eq!(ini, Ini::parse(SIMPLE_TEST_INI, IniMode::Simple).unwrap());
+}
+
+#[test]
+fn section_diff_test() {
+    let old = Ini::parse("[Section]\nkept=same\nremoved=old\nchanged=before\n", IniMode::Simple).unwrap();
+    let new = Ini::parse("[Section]\nkept=same\nadded=new\nchanged=after\n", IniMode::Simple).unwrap();
+    let old = old.get_section("Section").unwrap();
+    let new = new.get_section("Section").unwrap();
+
+    let diff = old.diff(new);
+    assert_eq!(diff.added, BTreeMap::from([("added".to_owned(), "new".to_owned())]));
+    assert_eq!(diff.removed, BTreeMap::from([("removed".to_owned(), "old".to_owned())]));
+    assert_eq!(diff.changed, BTreeMap::from([("changed".to_owned(), ("before".to_owned(), "after".to_owned()))]));
+    assert!(!diff.is_empty());
+    assert!(old.diff(old).is_empty());
+}
+
+#[test]
+fn validate_keys_test() {
+    let ini = Ini::parse("[Window]\nwidth=640\ntiemout=5\n[Widnow]\nheight=480\n", IniMode::Simple).unwrap();
+
+    let mut allowed: BTreeMap<&str, &[&str]> = BTreeMap::new();
+    allowed.insert("Window", &["width", "height", "timeout"]);
+
+    assert_eq!(ini.validate_keys(&allowed), Err(vec![
+        UnknownKey::Section { section: "Widnow".to_owned() },
+        UnknownKey::Key { section: "Window".to_owned(), key: "tiemout".to_owned() },
+    ]));
+
+    allowed.insert("Widnow", &["height"]);
+    allowed.insert("Window", &["width", "tiemout"]);
+    assert_eq!(ini.validate_keys(&allowed), Ok(()));
+}
+
+#[test]
+fn global_section_test() {
+    let flat = "name=gerbil\n; comment\ncolor = brown\n";
+    assert_eq!(Ini::parse(flat, IniMode::Simple), Err(IniParsingError::ExpectedSectionTitle { line_number: 1 }));
+
+    let options = IniOptions { global_section: true, ..IniMode::SimpleTrimmed.into() };
+    let ini = Ini::parse_with_options(flat, &options).unwrap();
+    assert_eq!(ini.get("name"), Some("gerbil"));
+    assert_eq!(ini.get("color"), Some("brown"));
+    assert_eq!(ini.get("missing"), None);
+
+    let ini = Ini::parse_with_options("top=level\n[Section]\nkey=value\n", &options).unwrap();
+    assert_eq!(ini.get("top"), Some("level"));
+    assert_eq!(ini.get("key"), None);
+    assert_eq!(ini.get_value("Section", "key"), Some("value"));
+
+    let ini = Ini::parse_with_options(SIMPLE_TRIMMED_TEST_INI, &options).unwrap();
+    assert_eq!(ini, Ini::parse(SIMPLE_TRIMMED_TEST_INI, IniMode::SimpleTrimmed).unwrap());
+    assert_eq!(ini.get("yourkey"), None);
+}
+
+#[test]
+fn parse_report_line_counts_test() {
+    let (ini, report) = Ini::parse_with_report(SIMPLE_TEST_INI, &IniMode::Simple.into()).unwrap();
+    assert_eq!(ini, Ini::parse(SIMPLE_TEST_INI, IniMode::Simple).unwrap());
+    assert_eq!(report.comment_lines, 5);
+    assert_eq!(report.blank_lines, 5);
+}
+
+#[test]
+fn escaped_comment_keys_test() {
+    let escaped = "[Section]\n\\;key=value\n\\#other=other value\n;comment=skipped\n\\plain=kept\n";
+    let options = IniOptions { escaped_comment_keys: true, ..Default::default() };
+    let ini = Ini::parse_with_options(escaped, &options).unwrap();
+    assert_eq!(ini.get_value("Section", ";key"), Some("value"));
+    assert_eq!(ini.get_value("Section", "#other"), Some("other value"));
+    assert_eq!(ini.get_value("Section", ";comment"), None);
+    assert_eq!(ini.get_value("Section", "\\plain"), Some("kept"));
+
+    let ini = Ini::parse(escaped, IniMode::Simple).unwrap();
+    assert_eq!(ini.get_value("Section", "\\;key"), Some("value"));
+}
+
+#[test]
+fn reparse_line_test() {
+    let options = IniOptions::from(IniMode::Simple);
+    let mut ini = Ini::parse(SIMPLE_TEST_INI, IniMode::Simple).unwrap();
+
+    ini.reparse_line(19, "yourkey=This is a value!", "yourkey=This is an edited value!", &options).unwrap();
+    assert_eq!(ini.get_value("Another Section", "yourkey"), Some("This is an edited value!"));
+
+    ini.reparse_line(19, "yourkey=This is an edited value!", "renamedkey=This is an edited value!", &options).unwrap();
+    assert_eq!(ini.get_value("Another Section", "yourkey"), None);
+    assert_eq!(ini.get_value("Another Section", "renamedkey"), Some("This is an edited value!"));
+
+    ini.reparse_line(10, "anotherkey=This is yet another value!", ";anotherkey=This is yet another value!", &options).unwrap();
+    assert_eq!(ini.get_value("My Section", "anotherkey"), None);
+
+    ini.reparse_line(2, "; This is a comment.", "; This is an edited comment.", &options).unwrap();
+    assert_eq!(ini.reparse_line(19, "renamedkey=This is an edited value!", "some KEY=x", &options), Err(IniParsingError::DuplicateSectionKey { line_number: 19, section: "Another Section".to_owned(), key: "some KEY".to_owned() }.into()));
+    assert_eq!(ini.reparse_line(19, "renamedkey=This is an edited value!", "broken", &options), Err(IniParsingError::MissingEquals { line_number: 19 }.into()));
+    assert_eq!(ini.get_value("Another Section", "renamedkey"), Some("This is an edited value!"));
+}
+
+#[test]
+fn reparse_line_header_test() {
+    let options = IniOptions::from(IniMode::Simple);
+    let mut ini = Ini::parse(SIMPLE_TEST_INI, IniMode::Simple).unwrap();
+    let original = ini.clone();
+
+    assert_eq!(ini.reparse_line(18, "[Another Section]", "[Renamed Section]", &options), Err(ReparseError::FullReparseRequired { line_number: 18 }));
+    assert_eq!(ini.reparse_line(8, "", "[New Section]", &options), Err(ReparseError::FullReparseRequired { line_number: 8 }));
+    assert_eq!(ini.reparse_line(8, "", "newkey=value", &options), Err(ReparseError::FullReparseRequired { line_number: 8 }));
+    assert_eq!(ini.reparse_line(7, "some KEY=Not the stored value", "some KEY=x", &options), Err(ReparseError::FullReparseRequired { line_number: 7 }));
+    assert_eq!(ini, original);
+}
+
+#[test]
+fn section_remove_test() {
+    let mut ini = Ini::parse("[Section]\nc=3\na=1\nd=4\nb=2\n", IniMode::Simple).unwrap();
+    let section = ini.sections.get_mut("Section").unwrap();
+
+    assert_eq!(section.remove("b"), Some("2".to_owned()));
+    assert_eq!(section.remove("b"), None);
+    assert_eq!(section.serialize("Section", IniMode::Simple), "[Section]\na=1\nc=3\nd=4\n");
+
+    assert_eq!(section.remove("a"), Some("1".to_owned()));
+    assert_eq!(section.serialize("Section", IniMode::Simple), "[Section]\nc=3\nd=4\n");
+}
+
+#[test]
+fn empty_title_is_global_test() {
+    let options = IniOptions { empty_title_is_global: true, global_section: true, ..Default::default() };
+    let ini = Ini::parse_with_options("top=level\n[Section]\nkey=value\n[]\nbottom=level\n[]\nlast=level\n", &options).unwrap();
+    assert_eq!(ini.get("top"), Some("level"));
+    assert_eq!(ini.get("bottom"), Some("level"));
+    assert_eq!(ini.get("last"), Some("level"));
+    assert_eq!(ini.get("key"), None);
+
+    let options = IniOptions { empty_title_is_global: true, ..Default::default() };
+    let ini = Ini::parse_with_options("[]\nkey=value\n", &options).unwrap();
+    assert_eq!(ini.get("key"), Some("value"));
+
+    assert_eq!(Ini::parse("[]\na=1\n[]\nb=2\n", IniMode::Simple), Err(IniParsingError::DuplicateSection { line_number: 3, section: "".to_owned() }));
+}
+
+#[test]
+fn placeholder_warning_test() {
+    let input = "[Database]\nhost=localhost\npassword=CHANGEME\nuser=TODO\n";
+    let options = IniOptions { placeholders: &["CHANGEME", "TODO"], ..Default::default() };
+
+    let (ini, report) = Ini::parse_with_report(input, &options).unwrap();
+    assert_eq!(ini.get_value("Database", "password"), Some("CHANGEME"));
+    assert_eq!(report.warnings, vec![
+        IniWarning::PlaceholderValue { line_number: 3, key: "password".to_owned() },
+        IniWarning::PlaceholderValue { line_number: 4, key: "user".to_owned() },
+    ]);
+
+    let (_, report) = Ini::parse_with_report(input, &IniMode::Simple.into()).unwrap();
+    assert!(report.warnings.is_empty());
+}
+
+#[test]
+fn retain_prefix_test() {
+    let mut ini = Ini::parse("[core]\na=1\n[plugin:audio]\nb=2\n[plugin:video]\nc=3\n[plugins]\nd=4\n", IniMode::Simple).unwrap();
+    ini.retain_prefix("plugin:");
+    assert_eq!(ini, Ini::parse("[plugin:audio]\nb=2\n[plugin:video]\nc=3\n", IniMode::Simple).unwrap());
+}
+
+#[test]
+fn section_eq_trimmed_test() {
+    let padded = Ini::parse("[Section]\na=  1 \nb=two\n", IniMode::Simple).unwrap();
+    let clean = Ini::parse("[Section]\na=1\nb=two\n", IniMode::Simple).unwrap();
+    let renamed = Ini::parse("[Section]\na =1\nb=two\n", IniMode::Simple).unwrap();
+    let padded = padded.get_section("Section").unwrap();
+    let clean = clean.get_section("Section").unwrap();
+    let renamed = renamed.get_section("Section").unwrap();
+
+    assert_ne!(padded, clean);
+    assert!(padded.eq_trimmed(clean));
+    assert!(!clean.eq_trimmed(renamed));
+}
+
+#[test]
+fn strip_carriage_returns_test() {
+    let input = "[Section]\r\nkey=before\rafter\r\n";
+    assert_eq!(Ini::parse(input, IniMode::Simple).unwrap().get_value("Section", "key"), Some("before\rafter"));
+
+    let options = IniOptions { strip_carriage_returns: true, ..Default::default() };
+    assert_eq!(Ini::parse_with_options(input, &options).unwrap().get_value("Section", "key"), Some("beforeafter"));
+}
+
+#[test]
+fn get_float_policy_test() {
+    let ini = Ini::parse("[Floats]\ninfinite=inf\nnegative=-inf\nnot a number=NaN\nnormal=2.5\n", IniMode::Simple).unwrap();
+    let section = ini.get_section("Floats").unwrap();
+
+    assert_eq!(section.get_f64("infinite", FloatPolicy::default()), Some(Ok(f64::INFINITY)));
+    assert_eq!(section.get_f32("negative", FloatPolicy::AllowNonFinite), Some(Ok(f32::NEG_INFINITY)));
+    assert!(section.get_f64("not a number", FloatPolicy::AllowNonFinite).unwrap().unwrap().is_nan());
+    assert!(section.get_f32("not a number", FloatPolicy::AllowNonFinite).unwrap().unwrap().is_nan());
+    assert_eq!(section.get_f64("normal", FloatPolicy::AllowNonFinite), Some(Ok(2.5)));
+    assert_eq!(section.get_f32("normal", FloatPolicy::AllowNonFinite), Some(Ok(2.5)));
+
+    assert_eq!(section.get_f64("infinite", FloatPolicy::FiniteOnly), Some(Err(IniValueError::Invalid)));
+    assert_eq!(section.get_f32("negative", FloatPolicy::FiniteOnly), Some(Err(IniValueError::Invalid)));
+    assert_eq!(section.get_f64("not a number", FloatPolicy::FiniteOnly), Some(Err(IniValueError::Invalid)));
+    assert_eq!(section.get_f32("normal", FloatPolicy::FiniteOnly), Some(Ok(2.5)));
+    assert_eq!(section.get_f64("missing", FloatPolicy::FiniteOnly), None);
+}
+
+#[test]
+fn get_any_test() {
+    let ini = Ini::parse(SIMPLE_TEST_INI, IniMode::Simple).unwrap();
+    assert_eq!(ini.get_any("yourkey"), Some(("Another Section", "This is a value!")));
+    assert_eq!(ini.get_any("some KEY"), Some(("Another Section", "This, too, is a value!")));
+    assert_eq!(ini.get_any("missing"), None);
+}
+
+#[test]
+fn section_map_keys_test() {
+    let ini = Ini::parse("[Section]\nName=first\nname=second\nColor=brown\n", IniMode::Simple).unwrap();
+    let original = ini.get_section("Section").unwrap();
+
+    let mut section = original.clone();
+    assert_eq!(section.map_keys(|key| key.to_lowercase(), DuplicateKeyPolicy::Error), Err(DuplicateKeyError { key: "name".to_owned() }));
+    assert_eq!(&section, original);
+
+    section.map_keys(|key| key.to_lowercase(), DuplicateKeyPolicy::LastWins).unwrap();
+    assert_eq!(section.get("name"), Some("second"));
+    assert_eq!(section.get("color"), Some("brown"));
+    assert_eq!(section.get("Color"), None);
+
+    section.map_keys(|key| key.to_uppercase(), DuplicateKeyPolicy::Error).unwrap();
+    assert_eq!(section.get("COLOR"), Some("brown"));
+}
+
+#[test]
+fn positional_access_test() {
+    let ini = Ini::parse(SIMPLE_TEST_INI, IniMode::Simple).unwrap();
+
+    let (name, section) = ini.section_at(1).unwrap();
+    assert_eq!(name, "My Section");
+    assert!(ini.section_at(2).is_none());
+
+    assert_eq!(section.key_at(1), Some(("some KEY", "This is a value!")));
+    assert_eq!(section.key_at(0), Some(("anotherkey", "This is yet another value!")));
+    assert_eq!(section.key_at(2), None);
+}
+
+#[test]
+fn stop_at_section_test() {
+    let input = "[Section]\nkey=value\n[__end__]\nthis is not ini data\n[Section]\n";
+    assert_eq!(Ini::parse(input, IniMode::Simple), Err(IniParsingError::MissingEquals { line_number: 4 }));
+
+    let options = IniOptions { stop_at_section: Some("__end__"), ..Default::default() };
+    let ini = Ini::parse_with_options(input, &options).unwrap();
+    assert_eq!(ini, Ini::parse("[Section]\nkey=value\n", IniMode::Simple).unwrap());
+}
+
+#[test]
+fn duplicate_key_policy_test() {
+    let input = "[servers]\nprimary=a.example\nprimary=b.example\n[strict]\nkey=1\nkey=2\n";
+    assert_eq!(Ini::parse(input, IniMode::Simple), Err(IniParsingError::DuplicateSectionKey { line_number: 3, section: "servers".to_owned(), key: "primary".to_owned() }));
+
+    let options = IniOptions { duplicate_keys: DuplicateKeyPolicy::LastWins, ..Default::default() };
+    let ini = Ini::parse_with_options(input, &options).unwrap();
+    assert_eq!(ini.get_value("servers", "primary"), Some("b.example"));
+    assert_eq!(ini.get_value("strict", "key"), Some("2"));
+
+    let options = IniOptions {
+        section_duplicate_keys: Some(&|section| if section == "servers" { DuplicateKeyPolicy::LastWins } else { DuplicateKeyPolicy::Error }),
+        ..Default::default()
+    };
+    assert_eq!(Ini::parse_with_options(input, &options), Err(IniParsingError::DuplicateSectionKey { line_number: 6, section: "strict".to_owned(), key: "key".to_owned() }));
+    let ini = Ini::parse_with_options("[servers]\nprimary=a.example\nprimary=b.example\n[strict]\nkey=1\n", &options).unwrap();
+    assert_eq!(ini.get_value("servers", "primary"), Some("b.example"));
+
+    let overridable = ["servers".to_owned(), "strict".to_owned()];
+    let policy = |section: &str| if overridable.iter().any(|s| s == section) { DuplicateKeyPolicy::LastWins } else { DuplicateKeyPolicy::Error };
+    let options = IniOptions { section_duplicate_keys: Some(&policy), ..Default::default() };
+    let ini = Ini::parse_with_options(input, &options).unwrap();
+    assert_eq!(ini.get_value("strict", "key"), Some("2"));
+    assert_eq!(Ini::parse_with_options("[other]\nkey=1\nkey=2\n", &options), Err(IniParsingError::DuplicateSectionKey { line_number: 3, section: "other".to_owned(), key: "key".to_owned() }));
+}
+
+#[test]
+fn entries_test() {
+    let mut ini = Ini::default();
+    let mut section = None;
+    for entry in Ini::entries(SIMPLE_TEST_INI, IniMode::Simple) {
+        match entry.unwrap() {
+            IniEvent::Section { name, .. } => {
+                ini.section_or_insert(&name);
+                section = Some(name);
+            },
+            IniEvent::KeyValue { key, value, .. } => {
+                ini.sections.get_mut(section.as_ref().unwrap()).unwrap().set(&key, &value);
+            }
+        }
+    }
+    assert_eq!(ini, Ini::parse(SIMPLE_TEST_INI, IniMode::Simple).unwrap());
+
+    let mut entries = Ini::entries("[Section]\nkey=value\nbroken\nafter=error\n", IniMode::Simple);
+    assert_eq!(entries.next(), Some(Ok(IniEvent::Section { line_number: 1, name: "Section".to_owned() })));
+    assert_eq!(entries.next(), Some(Ok(IniEvent::KeyValue { line_number: 2, key: "key".to_owned(), value: "value".to_owned() })));
+    assert_eq!(entries.next(), Some(Err(IniParsingError::MissingEquals { line_number: 3 })));
+    assert_eq!(entries.next(), None);
+}
+
+#[test]
+fn prelude_test() {
+    use crate::prelude::*;
+
+    let options = IniOptions { mode: IniMode::SimpleTrimmed, ..Default::default() };
+    let ini: Ini = Ini::parse_with_options(SIMPLE_TRIMMED_TEST_INI, &options).unwrap();
+    let section: &IniSection = ini.get_section("My Section").unwrap();
+    assert_eq!(section.value("some KEY").unwrap().as_i64(), Err(IniValueError::Invalid));
+    assert_eq!(Ini::parse("key=value", IniMode::Simple), Err(IniParsingError::ExpectedSectionTitle { line_number: 1 }));
+}
+
+#[test]
+fn resolve_test() {
+    let ini = Ini::parse("[paths]\nroot=/srv\ndata=${root}/data\n[app]\ncache=${paths:data}/cache\nloop=${loop}\nmissing=${paths:nothing}\nunterminated=${root\n", IniMode::Simple).unwrap();
+
+    assert_eq!(ini.resolve("paths", "root"), Some(Cow::Borrowed("/srv")));
+    assert_eq!(ini.resolve("app", "cache"), Some(Cow::Owned("/srv/data/cache".to_owned())));
+    assert_eq!(ini.get_value("app", "cache"), Some("${paths:data}/cache"));
+    assert_eq!(ini.resolve("app", "loop"), None);
+    assert_eq!(ini.resolve("app", "missing"), None);
+    assert_eq!(ini.resolve("app", "unterminated").as_deref(), Some("${root"));
+    assert_eq!(ini.resolve("app", "nothing"), None);
+
+    let ini = Ini::parse("[plugin:a]\nx=1\n[app]\nplugin=${plugin:a:x}\n", IniMode::Simple).unwrap();
+    assert_eq!(ini.resolve("app", "plugin").as_deref(), Some("1"));
+}
+
+#[test]
+fn duplicate_section_policy_test() {
+    let clean = "[Section]\na=1\nb=2\n[Other]\n[Section]\nc=3\nb=2\n";
+    let conflicting = "[Section]\na=1\n[Section]\na=2\n";
+    assert_eq!(Ini::parse(clean, IniMode::Simple), Err(IniParsingError::DuplicateSection { line_number: 5, section: "Section".to_owned() }));
+
+    let options = IniOptions { duplicate_sections: DuplicateSectionPolicy::MergeStrict, ..Default::default() };
+    let ini = Ini::parse_with_options(clean, &options).unwrap();
+    assert_eq!(ini, Ini::parse("[Section]\na=1\nb=2\nc=3\n[Other]\n", IniMode::Simple).unwrap());
+    assert_eq!(Ini::parse_with_options(conflicting, &options), Err(IniParsingError::DuplicateSectionKey { line_number: 4, section: "Section".to_owned(), key: "a".to_owned() }));
+    assert_eq!(Ini::parse_with_options("[Section]\na=1\na=1\n", &options), Err(IniParsingError::DuplicateSectionKey { line_number: 3, section: "Section".to_owned(), key: "a".to_owned() }));
+    assert_eq!(Ini::parse_with_options("[Section]\na=1\n[Section]\na=1\na=1\n", &options), Err(IniParsingError::DuplicateSectionKey { line_number: 5, section: "Section".to_owned(), key: "a".to_owned() }));
+
+    let options = IniOptions { duplicate_sections: DuplicateSectionPolicy::Merge, ..Default::default() };
+    assert_eq!(Ini::parse_with_options(conflicting, &options), Err(IniParsingError::DuplicateSectionKey { line_number: 4, section: "Section".to_owned(), key: "a".to_owned() }));
+    let options = IniOptions { duplicate_keys: DuplicateKeyPolicy::LastWins, ..options };
+    assert_eq!(Ini::parse_with_options(conflicting, &options).unwrap().get_value("Section", "a"), Some("2"));
+}
+
+#[test]
+fn section_subset_test() {
+    let ini = Ini::parse("[defaults]\nwidth=640\nheight=480\n[config]\nwidth=640\nheight=480\ndepth=24\n[changed]\nwidth=800\ndepth=24\n", IniMode::Simple).unwrap();
+    let defaults = ini.get_section("defaults").unwrap();
+    let config = ini.get_section("config").unwrap();
+    let changed = ini.get_section("changed").unwrap();
+
+    assert!(defaults.is_subset_of(config));
+    assert!(defaults.difference(config).is_empty());
+    assert!(!config.is_subset_of(defaults));
+
+    assert!(!defaults.is_subset_of(changed));
+    assert_eq!(defaults.difference(changed), vec!["height", "width"]);
+}
+
+#[test]
+fn get_value_trimmed_test() {
+    let ini = Ini::parse("[Section]\npadded=  some value \t\n", IniMode::Simple).unwrap();
+    let section = ini.get_section("Section").unwrap();
+    assert_eq!(section.get("padded"), Some("  some value \t"));
+    assert_eq!(section.get_value_trimmed("padded"), Some("some value"));
+    assert_eq!(section.get_value_trimmed("missing"), None);
+}
+
+#[test]
+fn access_tracking_test() {
+    let ini = Ini::parse("[Window]\nwidth=640\nheight=480\n", IniMode::Simple).unwrap();
+    let tracked = ini.with_access_tracking();
+    assert_eq!(tracked.unused_keys(), vec![("Window", "height"), ("Window", "width")]);
+
+    assert_eq!(tracked.get_value("Window", "width"), Some("640"));
+    assert_eq!(tracked.get_value("Window", "depth"), None);
+    assert_eq!(tracked.unused_keys(), vec![("Window", "height")]);
+}
+
+#[test]
+fn forbid_surrounding_whitespace_test() {
+    let options = IniOptions { forbid_surrounding_whitespace: true, ..Default::default() };
+    assert_eq!(Ini::parse_with_options(SIMPLE_TEST_INI, &options), Ini::parse(SIMPLE_TEST_INI, IniMode::Simple));
+    assert_eq!(Ini::parse_with_options("[Section]\nkey= value\n", &options), Err(IniParsingError::NonCanonicalWhitespace { line_number: 2 }));
+    assert_eq!(Ini::parse_with_options("[Section]\nkey =value\n", &options), Err(IniParsingError::NonCanonicalWhitespace { line_number: 2 }));
+    assert_eq!(Ini::parse_with_options("[Section]\n key=value\n", &options), Err(IniParsingError::NonCanonicalWhitespace { line_number: 2 }));
+
+    let options = IniOptions { mode: IniMode::SimpleTrimmed, ..options };
+    assert_eq!(Ini::parse_with_options(SIMPLE_TRIMMED_TEST_INI, &options), Err(IniParsingError::NonCanonicalWhitespace { line_number: 7 }));
+
+    let options = IniOptions { valueless_keys: true, ..options };
+    assert_eq!(Ini::parse_with_options("[Section]\ndebug \n", &options), Err(IniParsingError::NonCanonicalWhitespace { line_number: 2 }));
+    assert_eq!(Ini::parse_with_options("[Section]\ndebug\n", &options).unwrap().get_value("Section", "debug"), Some(""));
+}
+
+#[test]
+fn section_names_in_order_test() {
+    let mut ini = Ini::parse("[zeta]\n[alpha]\nkey=value\n[mu]\n", IniMode::Simple).unwrap();
+    assert_eq!(ini.section_names_in_order().collect::<Vec<_>>(), vec!["zeta", "alpha", "mu"]);
+    assert_eq!(ini, Ini::parse("[alpha]\nkey=value\n[mu]\n[zeta]\n", IniMode::Simple).unwrap());
+
+    ini.set_value("beta", "key", "value");
+    ini.set_value("alpha", "other", "value");
+    assert_eq!(ini.section_names_in_order().collect::<Vec<_>>(), vec!["zeta", "alpha", "mu", "beta"]);
+
+    ini.retain_prefix("m");
+    assert_eq!(ini.section_names_in_order().collect::<Vec<_>>(), vec!["mu"]);
+
+    let ini = IniBuilder::new().section("b").section("a").build().unwrap();
+    assert_eq!(ini.section_names_in_order().collect::<Vec<_>>(), vec!["b", "a"]);
+}
+
+#[test]
+fn try_from_bytes_test() {
+    assert_eq!(Ini::try_from(SIMPLE_TEST_INI.as_bytes()), Ok(Ini::parse(SIMPLE_TEST_INI, IniMode::Simple).unwrap()));
+    assert!(matches!(Ini::try_from(&b"[Section]\nkey=\xFF\n"[..]), Err(IniBytesError::Utf8(_))));
+    assert_eq!(Ini::try_from(&b"key=value\n"[..]), Err(IniBytesError::Parse(IniParsingError::ExpectedSectionTitle { line_number: 1 })));
+}
+
+#[test]
+fn section_to_vec_test() {
+    let ini = Ini::parse(SIMPLE_TEST_INI, IniMode::Simple).unwrap();
+    let section = ini.get_section("My Section").unwrap();
+    assert_eq!(section.to_vec(), vec![
+        ("anotherkey".to_owned(), "This is yet another value!".to_owned()),
+        ("some KEY".to_owned(), "This is a value!".to_owned()),
+    ]);
+}
+
+#[test]
+fn remove_empty_test() {
+    let mut ini = Ini::parse("[a]\nunset=\nblank=  \nset=value\n[b]\nunset=\n", IniMode::Simple).unwrap();
+
+    let mut section = ini.get_section("a").unwrap().clone();
+    section.remove_empty();
+    assert_eq!(section.to_vec(), vec![("set".to_owned(), "value".to_owned())]);
+
+    ini.remove_empty();
+    assert_eq!(ini, Ini::parse("[a]\nset=value\n[b]\n", IniMode::Simple).unwrap());
+}
+
+#[test]
+fn trailing_empty_section_test() {
+    let truncated = "[Section]\nkey=value\n\n[Truncated]\n; nothing here\n";
+    let (_, report) = Ini::parse_with_report(truncated, &IniMode::Simple.into()).unwrap();
+    assert!(report.warnings.is_empty());
+
+    let options = IniOptions { trailing_empty_section: EmptySectionPolicy::Warn, ..Default::default() };
+    let (ini, report) = Ini::parse_with_report(truncated, &options).unwrap();
+    assert!(ini.get_section("Truncated").is_some());
+    assert_eq!(report.warnings, vec![IniWarning::TrailingEmptySection { line_number: 4, section: "Truncated".to_owned() }]);
+
+    let (_, report) = Ini::parse_with_report("[Empty]\n[Section]\nkey=value\n", &options).unwrap();
+    assert!(report.warnings.is_empty());
+
+    let options = IniOptions { trailing_empty_section: EmptySectionPolicy::Error, ..Default::default() };
+    assert_eq!(Ini::parse_with_options(truncated, &options), Err(IniParsingError::TrailingEmptySection { line_number: 4, section: "Truncated".to_owned() }));
+}
+
+#[test]
+fn collapse_value_whitespace_test() {
+    let ini = "[Section]\ndescription=  A   long \t description  \nescaped=one  \\n  two\n";
+    let options = IniOptions { collapse_value_whitespace: true, newline_escapes: true, ..Default::default() };
+    let ini = Ini::parse_with_options(ini, &options).unwrap();
+    let section = ini.get_section("Section").unwrap();
+    assert_eq!(section.get("description"), Some("A long description"));
+    assert_eq!(section.get("escaped"), Some("one \n two"));
+
+    let ini = "[Section]\nquoted=\"a    b\"\nplain=  a    b  \n";
+    for options in [
+        IniOptions { collapse_value_whitespace: true, quoted_values: true, ..Default::default() },
+        IniOptions { collapse_value_whitespace: true, strip_surrounding_quotes: true, ..Default::default() }
+    ] {
+        let ini = Ini::parse_with_options(ini, &options).unwrap();
+        let section = ini.get_section("Section").unwrap();
+        assert_eq!(section.get("quoted"), Some("a    b"));
+        assert_eq!(section.get("plain"), Some("a b"));
+    }
+}
+
+#[test]
+fn get_value_bytes_test() {
+    let ini = Ini::parse("[Keys]\nsecret=héllo\n", IniMode::Simple).unwrap();
+    assert_eq!(ini.get_value_bytes("Keys", "secret"), Some("héllo".as_bytes()));
+    assert_eq!(ini.get_value_bytes("Keys", "secret"), ini.get_value("Keys", "secret").map(str::as_bytes));
+    assert_eq!(ini.get_value_bytes("Keys", "missing"), None);
+}
+
+#[test]
+fn fingerprint_test() {
+    let ini = Ini::parse("[My Section]\nsome value=this is a value\nanother value=this is another value\n\n[Another Section]\nkey=value\n", IniMode::Simple).unwrap();
+    let reordered = Ini::parse("[Another Section]\nkey=value\n\n[My Section]\nanother value=this is another value\nsome value=this is a value\n", IniMode::Simple).unwrap();
+    assert_eq!(ini, reordered);
+    assert_eq!(ini.fingerprint(), reordered.fingerprint());
+    assert_eq!(ini.fingerprint(), ini.clone().fingerprint());
+
+    let mut changed = ini.clone();
+    changed.set_value("My Section", "some value", "this is a changed value");
+    assert_ne!(ini.fingerprint(), changed.fingerprint());
+
+    // Moving text between keys and values must change the fingerprint.
+    let a = Ini::parse("[s]\nab=c\n", IniMode::Simple).unwrap();
+    let b = Ini::parse("[s]\na=bc\n", IniMode::Simple).unwrap();
+    assert_ne!(a.fingerprint(), b.fingerprint());
+}
+
+#[test]
+fn merge_with_test() {
+    let mut ini = Ini::parse("[Paths]\nsearch=/usr/lib\nhome=/home/me\n", IniMode::Simple).unwrap();
+    let other = Ini::parse("[Paths]\nsearch=/opt/lib\nhome=/home/me\ncache=/tmp\n\n[New]\nkey=value\n", IniMode::Simple).unwrap();
+
+    let mut conflicts = Vec::new();
+    ini.merge_with(&other, |section, key, existing, incoming| {
+        conflicts.push((section.to_owned(), key.to_owned()));
+        alloc::format!("{existing}:{incoming}")
+    });
+
+    assert_eq!(conflicts, vec![("Paths".to_owned(), "search".to_owned())]);
+    assert_eq!(ini.get_value("Paths", "search"), Some("/usr/lib:/opt/lib"));
+    assert_eq!(ini.get_value("Paths", "home"), Some("/home/me"));
+    assert_eq!(ini.get_value("Paths", "cache"), Some("/tmp"));
+    assert_eq!(ini.get_value("New", "key"), Some("value"));
+    assert_eq!(ini.section_names_in_order().collect::<Vec<_>>(), ["Paths", "New"]);
+}
+
+#[test]
+fn comments_must_be_line_start_test() {
+    let indented = "[Section]\n    ; indented comment\nkey=value\n";
+    assert!(IniOptions::default().comments_must_be_line_start);
+    assert_eq!(Ini::parse(indented, IniMode::Simple), Err(IniParsingError::MissingEquals { line_number: 2 }));
+
+    let options = IniOptions { comments_must_be_line_start: false, ..Default::default() };
+    let (ini, report) = Ini::parse_with_report(indented, &options).unwrap();
+    assert_eq!(report.comment_lines, 1);
+    assert_eq!(ini.get_value("Section", "key"), Some("value"));
+
+    // An indented comment containing `=` is otherwise read as a key.
+    let ini = Ini::parse("[Section]\n\t; a=b\n", IniMode::Simple).unwrap();
+    assert_eq!(ini.get_value("Section", "\t; a"), Some("b"));
+}
+
+#[test]
+fn get_cow_test() {
+    let options = IniOptions { newline_escapes: true, ..Default::default() };
+    let ini = Ini::parse_with_options("[Section]\nkey=value\nescaped=one\\ntwo\n", &options).unwrap();
+    let section = ini.get_section("Section").unwrap();
+    assert!(matches!(section.get_cow("key"), Some(Cow::Borrowed("value"))));
+    assert!(matches!(section.get_cow("escaped"), Some(Cow::Borrowed("one\ntwo"))));
+    assert_eq!(section.get_cow("missing"), None);
+}
+
+#[test]
+fn prefixed_test() {
+    let ini = Ini::parse("[Database]\nhost=localhost\nport=5432\n", IniMode::Simple).unwrap();
+    let section = ini.get_section("Database").unwrap();
+    let prefixed = section.prefixed("db.");
+    assert_eq!(prefixed.get("db.host"), Some("localhost"));
+    assert_eq!(prefixed.get("db.port"), Some("5432"));
+    assert_eq!(prefixed.get("host"), None);
+    assert_eq!(section.get("host"), Some("localhost"));
+}
+
+#[test]
+fn max_duplicates_per_key_test() {
+    let ini = "[Section]\nkey=1\nkey=2\nkey=3\nother=1\n";
+    let options = IniOptions { duplicate_keys: DuplicateKeyPolicy::LastWins, max_duplicates_per_key: Some(2), ..Default::default() };
+    assert_eq!(Ini::parse_with_options(ini, &options).unwrap().get_value("Section", "key"), Some("3"));
+
+    let options = IniOptions { max_duplicates_per_key: Some(1), ..options };
+    assert_eq!(Ini::parse_with_options(ini, &options), Err(IniParsingError::LimitExceeded { line_number: 4, section: "Section".to_owned(), key: "key".to_owned() }));
+
+    let options = IniOptions { duplicate_keys: DuplicateKeyPolicy::LastWins, ..Default::default() };
+    assert_eq!(Ini::parse_with_options(ini, &options).unwrap().get_value("Section", "key"), Some("3"));
+}
+
+#[test]
+fn get_unquoted_test() {
+    let ini = Ini::parse("[Section]\ndouble=\"x\"\nsingle='x'\nplain=x\nmismatched=\"x'\nlone=\"\nempty=\"\"\n", IniMode::Simple).unwrap();
+    let section = ini.get_section("Section").unwrap();
+    assert_eq!(section.get_unquoted("double"), Some("x"));
+    assert_eq!(section.get_unquoted("single"), Some("x"));
+    assert_eq!(section.get_unquoted("plain"), Some("x"));
+    assert_eq!(section.get_unquoted("mismatched"), Some("\"x'"));
+    assert_eq!(section.get_unquoted("lone"), Some("\""));
+    assert_eq!(section.get_unquoted("empty"), Some(""));
+    assert_eq!(section.get_unquoted("missing"), None);
+}
+
+#[test]
+fn flag_test() {
+    let ini = "[Features]\ndebug\nverbose=yes\nfast=off\n";
+    assert_eq!(Ini::parse(ini, IniMode::Simple), Err(IniParsingError::MissingEquals { line_number: 2 }));
+
+    let options = IniOptions { valueless_keys: true, ..Default::default() };
+    let ini = Ini::parse_with_options(ini, &options).unwrap();
+    let section = ini.get_section("Features").unwrap();
+    assert_eq!(section.get("debug"), Some(""));
+    assert!(section.flag("debug"));
+    assert!(section.flag("verbose"));
+    assert!(!section.flag("fast"));
+    assert!(!section.flag("absent"));
+}
+
+#[test]
+fn folded_continuations_test() {
+    let options = IniOptions { folded_continuations: true, ..Default::default() };
+    let ini = Ini::parse_with_options("[Message]\nsubject=A long\n  folded subject\nto=someone\n", &options).unwrap();
+    assert_eq!(ini.get_value("Message", "subject"), Some("A long  folded subject"));
+    assert_eq!(ini.get_value("Message", "to"), Some("someone"));
+
+    assert_eq!(Ini::parse_with_options("[Message]\n  not a continuation\n", &options), Err(IniParsingError::UnexpectedContinuation { line_number: 2 }));
+    assert_eq!(Ini::parse_with_options("[Message]\nkey=value\n\n  too late\n", &options), Err(IniParsingError::UnexpectedContinuation { line_number: 4 }));
+    assert_eq!(Ini::parse("[Message]\nsubject=A long\n  folded subject\n", IniMode::Simple), Err(IniParsingError::MissingEquals { line_number: 3 }));
+}
+
+#[test]
+fn folded_continuations_value_checks_test() {
+    let options = IniOptions { folded_continuations: true, duplicate_sections: DuplicateSectionPolicy::MergeStrict, ..Default::default() };
+    assert_eq!(Ini::parse_with_options("[S]\nk=a\n[S]\nk=a\n more\n", &options), Err(IniParsingError::DuplicateSectionKey { line_number: 4, section: "S".to_owned(), key: "k".to_owned() }));
+    assert_eq!(Ini::parse_with_options("[S]\nk=a\n more\n[S]\nk=a\n more\n", &options).unwrap().get_value("S", "k"), Some("a more"));
+
+    let options = IniOptions { folded_continuations: true, forbid_value_tabs: true, ..Default::default() };
+    assert_eq!(Ini::parse_with_options("[S]\nk=a\n b\tc\n", &options), Err(IniParsingError::TabInValue { line_number: 2 }));
+
+    let options = IniOptions { folded_continuations: true, comments_must_be_line_start: false, ..Default::default() };
+    let (ini, report) = Ini::parse_with_report("[S]\nk=a\n  ; comment\n", &options).unwrap();
+    assert_eq!(ini.get_value("S", "k"), Some("a"));
+    assert_eq!(report.comment_lines, 1);
+
+    let options = IniOptions { folded_continuations: true, newline_escapes: true, placeholders: &["CHANGE ME"], ..Default::default() };
+    let (ini, report) = Ini::parse_with_report("[S]\nk=CHANGE\n ME\nescaped=a\n \\nb\n", &options).unwrap();
+    assert_eq!(report.warnings, vec![IniWarning::PlaceholderValue { line_number: 2, key: "k".to_owned() }]);
+    assert_eq!(ini.get_value("S", "escaped"), Some("a \nb"));
+
+    let events = Ini::entries_with_options("[S]\nk=a\n b\n", options).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(events[1], IniEvent::KeyValue { line_number: 2, key: "k".to_owned(), value: "a b".to_owned() });
+}
+
+#[test]
+fn parsing_error_order_test() {
+    let mut errors = vec![
+        IniParsingError::TabInValue { line_number: 7 },
+        IniParsingError::DuplicateSection { line_number: 3, section: "b".to_owned() },
+        IniParsingError::MissingEquals { line_number: 12 },
+        IniParsingError::MissingEquals { line_number: 3 },
+        IniParsingError::DuplicateSection { line_number: 3, section: "a".to_owned() },
+    ];
+    errors.sort();
+    assert_eq!(errors, vec![
+        IniParsingError::MissingEquals { line_number: 3 },
+        IniParsingError::DuplicateSection { line_number: 3, section: "a".to_owned() },
+        IniParsingError::DuplicateSection { line_number: 3, section: "b".to_owned() },
+        IniParsingError::TabInValue { line_number: 7 },
+        IniParsingError::MissingEquals { line_number: 12 },
+    ]);
+    assert_eq!(errors.iter().map(IniParsingError::line_number).collect::<Vec<_>>(), [3, 3, 3, 7, 12]);
+}
+
+#[test]
+fn ini_patch_test() {
+    let old = Ini::parse("[Kept]\na=1\nb=2\nc=3\n\n[Old]\nkey=value\n\n[Same]\nkey=value\n", IniMode::Simple).unwrap();
+    let new = Ini::parse("[Kept]\na=1\nb=20\nd=4\n\n[New]\nkey=value\n\n[Same]\nkey=value\n", IniMode::Simple).unwrap();
+
+    let diff = old.diff(&new);
+    assert_eq!(diff.added.keys().collect::<Vec<_>>(), ["New"]);
+    assert_eq!(diff.removed.keys().collect::<Vec<_>>(), ["Old"]);
+    assert_eq!(diff.changed.keys().collect::<Vec<_>>(), ["Kept"]);
+    assert!(old.diff(&old).is_empty());
+
+    assert_eq!(diff.to_ini_patch(), "[Kept]\n+b=20\n-c\n+d=4\n\n[New]\n+key=value\n\n-[Old]\n");
+}
+
+#[test]
+fn non_ascii_whitespace_test() {
+    let ini = Ini::parse("[Section]\n \u{a0}\u{3000}\t\n\u{a0}key=value\n", IniMode::Simple).unwrap();
+    assert_eq!(ini.get_value("Section", "\u{a0}key"), Some("value"));
+    assert_eq!(Ini::parse("[Section]\n \u{a0}x\n", IniMode::Simple), Err(IniParsingError::MissingEquals { line_number: 2 }));
+    let (_, report) = Ini::parse_with_report("[Section]\n\u{b}\u{c}\n", &IniMode::Simple.into()).unwrap();
+    assert_eq!(report.blank_lines, 1);
+}
+
+#[test]
+fn get_or_tracked_test() {
+    let ini = Ini::parse("[Section]\nkey=value\n", IniMode::Simple).unwrap();
+    let section = ini.get_section("Section").unwrap();
+    assert_eq!(section.get_or_tracked("key", "default"), ("value", false));
+    assert_eq!(section.get_or_tracked("missing", "default"), ("default", true));
+}
+
+#[test]
+fn implicit_section_name_test() {
+    let ini = "name=example\n\n[Other]\nkey=value\n";
+    let options = IniOptions { implicit_section_name: Some("General"), global_section: true, ..Default::default() };
+    let parsed = Ini::parse_with_options(ini, &options).unwrap();
+    assert_eq!(parsed.get_value("General", "name"), Some("example"));
+    assert_eq!(parsed.get("name"), None);
+    assert_eq!(parsed.section_names_in_order().collect::<Vec<_>>(), ["General", "Other"]);
+
+    let duplicate = "name=example\n[General]\n";
+    assert_eq!(Ini::parse_with_options(duplicate, &options), Err(IniParsingError::DuplicateSection { line_number: 2, section: "General".to_owned() }));
+
+    let options = IniOptions { normalize_section_names: true, ..options };
+    assert_eq!(Ini::parse_with_options("k=v\n[General]\n", &options), Err(IniParsingError::DuplicateSection { line_number: 2, section: "general".to_owned() }));
+    let parsed = Ini::parse_with_options("k=v\n", &options).unwrap();
+    assert_eq!(parsed.section_names_in_order().collect::<Vec<_>>(), ["general"]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn get_socket_addrs_test() {
+    use std::net::SocketAddr;
+
+    let ini = Ini::parse("[Cluster]\nhosts=1.2.3.4:80, 5.6.7.8:80\nbroken=1.2.3.4:80, 5.6.7.8\n", IniMode::Simple).unwrap();
+    let section = ini.get_section("Cluster").unwrap();
+    let expected: Vec<SocketAddr> = vec!["1.2.3.4:80".parse().unwrap(), "5.6.7.8:80".parse().unwrap()];
+    assert_eq!(section.get_socket_addrs("hosts", ','), Some(Ok(expected)));
+
+    let error = section.get_socket_addrs("broken", ',').unwrap().unwrap_err();
+    assert_eq!(error.index, 1);
+    assert_eq!(error.element, "5.6.7.8");
+    assert!(section.get_socket_addrs("missing", ',').is_none());
+}
+
+#[test]
+fn long_folded_continuation_test() {
+    let mut ini = "[Section]\nkey=start\n".to_owned();
+    let mut expected = "start".to_owned();
+    for i in 0..1000 {
+        let line = alloc::format!(" line {i}");
+        ini += &line;
+        ini.push('\n');
+        expected += &line;
+    }
+    let options = IniOptions { folded_continuations: true, ..Default::default() };
+    assert_eq!(Ini::parse_with_options(&ini, &options).unwrap().get_value("Section", "key"), Some(expected.as_str()));
+}
+
+#[test]
+fn get_key_value_test() {
+    let ini = Ini::parse("[Section]\nSome Key=value\n", IniMode::Simple).unwrap();
+    let section = ini.get_section("Section").unwrap();
+    assert_eq!(section.get_key_value("Some Key"), Some(("Some Key", "value")));
+    assert_eq!(section.get_key_value("some key"), None);
+}
+
+#[test]
+fn get_try_into_test() {
+    #[derive(Debug, PartialEq)]
+    enum Level {
+        Low,
+        High
+    }
+
+    impl TryFrom<&str> for Level {
+        type Error = ();
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            match value {
+                "low" => Ok(Level::Low),
+                "high" => Ok(Level::High),
+                _ => Err(())
+            }
+        }
+    }
+
+    let ini = Ini::parse("[Section]\nlow=low\nhigh=high\nbad=medium\n", IniMode::Simple).unwrap();
+    let section = ini.get_section("Section").unwrap();
+    assert_eq!(section.get_try_into::<Level>("low"), Some(Ok(Level::Low)));
+    assert_eq!(section.get_try_into::<Level>("high"), Some(Ok(Level::High)));
+    assert_eq!(section.get_try_into::<Level>("bad"), Some(Err(())));
+    assert_eq!(section.get_try_into::<Level>("missing"), None);
+}
+
+#[test]
+fn parse_until_test() {
+    let ini = "[First]\na=1\n\n[Second]\nb=2\n\n[Third]\nc=3\n";
+    let parsed = Ini::parse_until(ini, "Second", &IniMode::Simple.into()).unwrap();
+    assert_eq!(parsed.get_value("First", "a"), Some("1"));
+    assert!(parsed.get_section("Second").is_none());
+    assert!(parsed.get_section("Third").is_none());
+
+    let parsed = Ini::parse_until(ini, "Third", &IniMode::Simple.into()).unwrap();
+    assert_eq!(parsed.section_names_in_order().collect::<Vec<_>>(), ["First", "Second"]);
+}
+
+#[test]
+fn strip_surrounding_quotes_test() {
+    let ini = "[Section]\ndouble= \"a value\" \nsingle='a value'\nplain=a value\ninner=\"a \"quoted\" value\"\nmismatched='a value\"\n";
+    let options = IniOptions { strip_surrounding_quotes: true, ..Default::default() };
+    let parsed = Ini::parse_with_options(ini, &options).unwrap();
+    let section = parsed.get_section("Section").unwrap();
+    assert_eq!(section.get("double"), Some("a value"));
+    assert_eq!(section.get("single"), Some("a value"));
+    assert_eq!(section.get("plain"), Some("a value"));
+    assert_eq!(section.get("inner"), Some("a \"quoted\" value"));
+    assert_eq!(section.get("mismatched"), Some("'a value\""));
+
+    let parsed = Ini::parse(ini, IniMode::Simple).unwrap();
+    assert_eq!(parsed.get_value("Section", "single"), Some("'a value'"));
+}
+
+#[test]
+fn rename_or_merge_key_test() {
+    let ini = Ini::parse("[Network]\nold_timeout=30\ntimeout=10\nold_retries=3\n", IniMode::Simple).unwrap();
+    let mut section = ini.get_section("Network").unwrap().clone();
+
+    let max = |old: &str, new: Option<&str>| match new {
+        Some(new) => alloc::format!("{}", old.parse::<u32>().unwrap().max(new.parse().unwrap())),
+        None => old.to_owned()
+    };
+    assert!(section.rename_or_merge_key("old_timeout", "timeout", max));
+    assert!(section.rename_or_merge_key("old_retries", "retries", max));
+    assert!(!section.rename_or_merge_key("old_missing", "missing", max));
+
+    assert_eq!(section.get("timeout"), Some("30"));
+    assert_eq!(section.get("retries"), Some("3"));
+    assert_eq!(section.get("old_timeout"), None);
+    assert_eq!(section.get("old_retries"), None);
+    assert_eq!(section.get("missing"), None);
+}
+
+#[test]
+fn entries_source_order_test() {
+    let ini = "[Zebra]\nz=1\ny=2\n\n[Apple]\nb=3\na=4\n";
+    let events = Ini::entries(ini, IniMode::Simple).collect::<Result<Vec<_>, _>>().unwrap();
+    let names = events.iter().map(|event| match event {
+        IniEvent::Section { name, .. } => name.as_str(),
+        IniEvent::KeyValue { key, .. } => key.as_str()
+    });
+    assert_eq!(names.collect::<Vec<_>>(), ["Zebra", "z", "y", "Apple", "b", "a"]);
+}
+
+#[test]
+fn load_all_test() {
+    #[derive(Debug, PartialEq)]
+    struct Server {
+        port: i64,
+        enabled: bool
+    }
+
+    impl FromIniSection for Server {
+        fn from_section(section: &IniSection) -> Result<Self, IniValueError> {
+            let port = section.value("port").ok_or(IniValueError::Invalid)?.as_i64()?;
+            let enabled = section.value("enabled").map_or(Ok(true), |v| v.as_bool())?;
+            Ok(Server { port, enabled })
+        }
+    }
+
+    let ini = Ini::parse("[web]\nport=80\n\n[admin]\nport=8080\nenabled=no\n\n[broken]\nport=eighty\n", IniMode::Simple).unwrap();
+    let servers = ini.load_all::<Server>();
+    assert_eq!(servers.len(), 3);
+    assert_eq!(servers["web"], Ok(Server { port: 80, enabled: true }));
+    assert_eq!(servers["admin"], Ok(Server { port: 8080, enabled: false }));
+    assert_eq!(servers["broken"], Err(IniValueError::Invalid));
+}
+
+#[test]
+fn get_aliased_test() {
+    let ini = Ini::parse("[Theme]\ncolour=blue\nname=dark\n", IniMode::Simple).unwrap();
+    let section = ini.get_section("Theme").unwrap();
+    assert_eq!(section.get_aliased(&["color", "colour"]), Some("blue"));
+    assert_eq!(section.get_aliased(&["name", "colour"]), Some("dark"));
+    assert_eq!(section.get_aliased(&["size", "dimensions"]), None);
+    assert_eq!(section.get_aliased(&[]), None);
+}
+
+#[test]
+fn quick_check_test() {
+    let options = IniOptions::default();
+    for ini in [SIMPLE_TEST_INI, "[Section]\nkey=value\n", "key=value\n", "[Section\n", "[Section]\nno equals\n", ""] {
+        assert_eq!(Ini::quick_check(ini, &options), Ini::parse_with_options(ini, &options).map(|_| ()), "{ini}");
+    }
+
+    let options = IniOptions { global_section: true, folded_continuations: true, ..Default::default() };
+    for ini in ["key=value\n  continued\n", "  continued\n", "[Section]\n\n  continued\n"] {
+        assert_eq!(Ini::quick_check(ini, &options), Ini::parse_with_options(ini, &options).map(|_| ()), "{ini}");
+    }
+
+    let options = IniOptions { trailing_empty_section: EmptySectionPolicy::Error, ..Default::default() };
+    for ini in ["[a]\nkey=value\n[b]\n", "[a]\n[b]\nkey=value\n"] {
+        assert_eq!(Ini::quick_check(ini, &options), Ini::parse_with_options(ini, &options).map(|_| ()), "{ini}");
+    }
+
+    // Duplicates are only detected by a full parse.
+    assert!(Ini::quick_check("[a]\n[a]\n", &IniOptions::default()).is_ok());
+}
+
+#[test]
+fn value_processing_test() {
+    let ini = "[Section]\nkey=  \"x\"  \n";
+
+    let options = IniOptions { value_processing: &[ValueProcessingStep::Trim, ValueProcessingStep::Unquote], ..Default::default() };
+    assert_eq!(Ini::parse_with_options(ini, &options).unwrap().get_value("Section", "key"), Some("x"));
+
+    let options = IniOptions { value_processing: &[ValueProcessingStep::Unquote, ValueProcessingStep::Trim], ..Default::default() };
+    assert_eq!(Ini::parse_with_options(ini, &options).unwrap().get_value("Section", "key"), Some("\"x\""));
+}
+
+#[test]
+fn inline_comments_test() {
+    let ini = "[Section]\ncut=val ;c\nkept=val;c\ntab=val\t# c\nempty= ; nothing\n";
+    let options = IniOptions { inline_comments: true, ..Default::default() };
+    let parsed = Ini::parse_with_options(ini, &options).unwrap();
+    let section = parsed.get_section("Section").unwrap();
+    assert_eq!(section.get("cut"), Some("val"));
+    assert_eq!(section.get("kept"), Some("val;c"));
+    assert_eq!(section.get("tab"), Some("val"));
+    assert_eq!(section.get("empty"), Some(""));
+
+    let parsed = Ini::parse(ini, IniMode::Simple).unwrap();
+    assert_eq!(parsed.get_value("Section", "cut"), Some("val ;c"));
+
+    let options = IniOptions { mode: IniMode::SimpleTrimmed, inline_comments: true, ..Default::default() };
+    let parsed = Ini::parse_with_options("[Section]\nempty = ; note\ncut = val ;c\nkept = val;c\nleading =;c\n", &options).unwrap();
+    let section = parsed.get_section("Section").unwrap();
+    assert_eq!(section.get("empty"), Some(""));
+    assert_eq!(section.get("cut"), Some("val"));
+    assert_eq!(section.get("kept"), Some("val;c"));
+    assert_eq!(section.get("leading"), Some(";c"));
+
+    for mode in [IniMode::Simple, IniMode::SimpleTrimmed] {
+        let options = IniOptions { mode, valueless_keys: true, inline_comments: true, ..Default::default() };
+        let parsed = Ini::parse_with_options("[Section]\ndebug ; enable\nverbose\t# c\nkept;c\n", &options).unwrap();
+        let section = parsed.get_section("Section").unwrap();
+        assert_eq!(section.get("debug"), Some(""));
+        assert_eq!(section.get("verbose"), Some(""));
+        assert_eq!(section.get("kept;c"), Some(""));
+        assert_eq!(section.get("debug ; enable"), None);
+    }
+}
+
+#[test]
+fn numeric_keys_sorted_test() {
+    let ini = Ini::parse("[List]\n10=ten\n2=two\n1=one\nname=list\n", IniMode::Simple).unwrap();
+    let section = ini.get_section("List").unwrap();
+    assert_eq!(section.numeric_keys_sorted().collect::<Vec<_>>(), [(1, "one"), (2, "two"), (10, "ten")]);
+}
+
+#[test]
+fn can_set_test() {
+    let section = IniSection::default();
+    assert_eq!(section.can_set("key", "a value with = and ;", IniMode::Simple), Ok(()));
+    assert_eq!(section.can_set("key", "two\nlines", IniMode::Simple), Err(SetError::InvalidValue { key: "key".to_owned(), value: "two\nlines".to_owned() }));
+    assert_eq!(section.can_set("a=b", "value", IniMode::Simple), Err(SetError::InvalidKey { key: "a=b".to_owned() }));
+    assert_eq!(section.can_set(";key", "value", IniMode::Simple), Err(SetError::InvalidKey { key: ";key".to_owned() }));
+
+    assert_eq!(section.can_set("key", " padded", IniMode::Simple), Ok(()));
+    assert!(section.can_set("key", " padded", IniMode::SimpleTrimmed).is_err());
+    assert_eq!(section, IniSection::default());
+}
+
+#[test]
+fn comment_continuations_test() {
+    let ini = "[Section]\n; a comment \\\nthat continues\n; and again \\\n  indented \\\n[Not a section]\nkey=value\n";
+    let options = IniOptions { comment_continuations: true, folded_continuations: true, ..Default::default() };
+    let (parsed, report) = Ini::parse_with_report(ini, &options).unwrap();
+    assert_eq!(report.comment_lines, 5);
+    assert_eq!(parsed.section_names_in_order().collect::<Vec<_>>(), ["Section"]);
+    assert_eq!(parsed.get_value("Section", "key"), Some("value"));
+    assert_eq!(Ini::quick_check(ini, &options), Ok(()));
+    assert_eq!(Ini::entries_with_options(ini, options).count(), 2);
+
+    assert_eq!(Ini::parse(ini, IniMode::Simple), Err(IniParsingError::MissingEquals { line_number: 3 }));
+}
+
+#[test]
+fn iter_mut_test() {
+    let ini = Ini::parse("[Section]\na=1\nb=2\n", IniMode::Simple).unwrap();
+    let mut section = ini.get_section("Section").unwrap().clone();
+    for (key, value) in section.iter_mut() {
+        value.insert_str(0, key);
+    }
+    assert_eq!(section.get("a"), Some("a1"));
+    assert_eq!(section.get("b"), Some("b2"));
+}
+
+#[test]
+fn backslash_continuations_test() {
+    let options = IniOptions { backslash_continuations: true, ..Default::default() };
+    let ini = "[Section]\nkey=one \\\ntwo \\\nthree\nother=value\n";
+    assert_eq!(Ini::parse_with_options(ini, &options).unwrap().get_value("Section", "key"), Some("one two three"));
+    assert_eq!(Ini::quick_check(ini, &options), Ok(()));
+
+    let dangling = "[Section]\nkey=value\\";
+    assert_eq!(Ini::parse_with_options(dangling, &options), Err(IniParsingError::DanglingContinuation { line_number: 2 }));
+    assert_eq!(Ini::quick_check(dangling, &options), Err(IniParsingError::DanglingContinuation { line_number: 2 }));
+    assert_eq!(Ini::entries_with_options(dangling, options).last(), Some(Err(IniParsingError::DanglingContinuation { line_number: 2 })));
+
+    let options = IniOptions { dangling_continuation: DanglingContinuationPolicy::Literal, ..options };
+    assert_eq!(Ini::parse_with_options(dangling, &options).unwrap().get_value("Section", "key"), Some("value\\"));
+    assert_eq!(Ini::parse_with_options("[Section]\nkey=a\\\nb\\", &options).unwrap().get_value("Section", "key"), Some("ab\\"));
+}
+
+#[test]
+fn to_toml_like_string_test() {
+    let options = IniOptions { global_section: true, ..Default::default() };
+    let ini = Ini::parse_with_options("name=example\n\n[My Section]\nport=8080\nsome key=say \"hi\" \\ bye\n\n[server.main]\nhost=localhost\n", &options).unwrap();
+    assert_eq!(
+        ini.to_toml_like_string(),
+        "name = \"example\"\n\n[\"My Section\"]\nport = \"8080\"\n\"some key\" = \"say \\\"hi\\\" \\\\ bye\"\n\n[\"server.main\"]\nhost = \"localhost\"\n"
+    );
+}
+
+#[test]
+fn equivalent_test() {
+    let source = "[Section]\nkey= v \nother=value\n";
+    let simple = Ini::parse(source, IniMode::Simple).unwrap();
+    let trimmed = Ini::parse(source, IniMode::SimpleTrimmed).unwrap();
+    assert_ne!(simple, trimmed);
+    assert!(simple.equivalent(&trimmed));
+    assert!(trimmed.equivalent(&simple));
+
+    let different = Ini::parse("[Section]\nkey=w\nother=value\n", IniMode::Simple).unwrap();
+    assert!(!simple.equivalent(&different));
+    let renamed = Ini::parse("[Renamed]\nkey=v\nother=value\n", IniMode::Simple).unwrap();
+    assert!(!simple.equivalent(&renamed));
+}
+
+#[test]
+fn normalize_section_names_test() {
+    let ini = "[Server]\nhost=a\n\n[ server ]\nhost=b\n";
+    let parsed = Ini::parse(ini, IniMode::Simple).unwrap();
+    assert_eq!(parsed.section_names_in_order().collect::<Vec<_>>(), ["Server", " server "]);
+
+    let options = IniOptions { normalize_section_names: true, ..Default::default() };
+    assert_eq!(Ini::parse_with_options(ini, &options), Err(IniParsingError::DuplicateSection { line_number: 4, section: "server".to_owned() }));
+
+    let parsed = Ini::parse_with_options("[ My  Server ]\nhost=a\n", &options).unwrap();
+    assert_eq!(parsed.get_value("my server", "host"), Some("a"));
+}
+
+#[test]
+fn get_parsed_ctx_test() {
+    let ini = Ini::parse("[Server]\nport=8080\nthreads=many\n", IniMode::Simple).unwrap();
+    let section = ini.get_section("Server").unwrap();
+    assert_eq!(section.get_parsed_ctx::<u16>("port"), Some(Ok(8080)));
+    assert_eq!(section.get_parsed_ctx::<u16>("missing"), None);
+
+    let error = section.get_parsed_ctx::<u32>("threads").unwrap().unwrap_err();
+    assert_eq!(error, ParseFieldError { key: "threads".to_owned(), value: "many".to_owned() });
+}
+
+#[test]
+fn quoted_values_test() {
+    let ini = "[Section]\nquoted=\"a ; b\"\ncommented='a # b' ; comment\nplain=a ; b\npartial=\"a\" b ; c\n";
+    let options = IniOptions { quoted_values: true, ..Default::default() };
+    let parsed = Ini::parse_with_options(ini, &options).unwrap();
+    let section = parsed.get_section("Section").unwrap();
+    assert_eq!(section.get("quoted"), Some("a ; b"));
+    assert_eq!(section.get("commented"), Some("'a # b' ; comment"));
+
+    let options = IniOptions { quoted_values: true, inline_comments: true, ..Default::default() };
+    let parsed = Ini::parse_with_options(ini, &options).unwrap();
+    let section = parsed.get_section("Section").unwrap();
+    assert_eq!(section.get("quoted"), Some("a ; b"));
+    assert_eq!(section.get("commented"), Some("a # b"));
+    assert_eq!(section.get("plain"), Some("a"));
+    assert_eq!(section.get("partial"), Some("\"a\" b"));
+}
+
+#[test]
+fn skip_separator_lines_test() {
+    let ini = "=======\n[Section]\n-------\nkey=value\n  =-=-=  \n";
+    let options = IniOptions { skip_separator_lines: true, ..Default::default() };
+    let (parsed, report) = Ini::parse_with_report(ini, &options).unwrap();
+    assert_eq!(report.comment_lines, 3);
+    assert_eq!(parsed.get_section("Section").unwrap().to_vec(), [("key".to_owned(), "value".to_owned())]);
+
+    let options = IniOptions { global_section: true, ..Default::default() };
+    let parsed = Ini::parse_with_options("=======\n", &options).unwrap();
+    assert_eq!(parsed.get(""), Some("======"));
+}
+
+#[test]
+fn sections_with_prefix_mut_test() {
+    let mut ini = Ini::parse("[core]\nkey=value\n\n[plugin:a]\nenabled=yes\n\n[plugin:b]\nenabled=yes\n\n[plugins]\nenabled=yes\n", IniMode::Simple).unwrap();
+    for (_, section) in ini.sections_with_prefix_mut("plugin:") {
+        section.set("enabled", "no");
+    }
+
+    assert_eq!(ini.sections_with_prefix("plugin:").map(|(name, _)| name).collect::<Vec<_>>(), ["plugin:a", "plugin:b"]);
+    assert!(ini.sections_with_prefix("plugin:").all(|(_, section)| section.get("enabled") == Some("no")));
+    assert_eq!(ini.get_value("plugins", "enabled"), Some("yes"));
+    assert_eq!(ini.get_section("core").unwrap().get("enabled"), None);
+}
+
+#[test]
+fn to_message_test() {
+    let error = Ini::parse("[Section]\nno equals\n", IniMode::Simple).unwrap_err();
+    assert_eq!(error.to_message(), alloc::format!("{error}"));
+    assert_eq!(error.to_message(), "Parsing error on line 2: Missing an `=` to separate the key and value");
+}
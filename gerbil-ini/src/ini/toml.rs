@@ -0,0 +1,62 @@
+use alloc::string::String;
+use core::fmt::Write;
+use crate::ini::Ini;
+
+impl Ini {
+    /// Write the ini in a TOML-like format, with every value written as a TOML string.
+    ///
+    /// This is a best-effort export for rough interoperability with TOML tooling. Values are always strings, even if
+    /// they look like numbers or booleans, and section names containing `.` are quoted rather than read as nested
+    /// tables. The global section is written first, without a header. The output is not checked to be valid TOML; for
+    /// example, a section named the same as a key in the global section produces a conflicting definition.
+    pub fn to_toml_like_string(&self) -> String {
+        let mut output = String::new();
+
+        let global = self.sections.get("").map(|section| ("", section));
+        let named = self.sections.iter().filter(|(name, _)| !name.is_empty()).map(|(name, section)| (name.as_str(), section));
+        for (name, section) in global.into_iter().chain(named) {
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            if !name.is_empty() {
+                output.push('[');
+                write_key(name, &mut output);
+                output.push_str("]\n");
+            }
+            for (key, value) in &section.values {
+                write_key(key, &mut output);
+                output.push_str(" = ");
+                write_string(value, &mut output);
+                output.push('\n');
+            }
+        }
+
+        output
+    }
+}
+
+fn write_key(key: &str, output: &mut String) {
+    let bare = !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        output.push_str(key);
+    }
+    else {
+        write_string(key, output);
+    }
+}
+
+fn write_string(string: &str, output: &mut String) {
+    output.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c.is_control() => { let _ = write!(output, "\\u{:04X}", c as u32); },
+            c => output.push(c)
+        }
+    }
+    output.push('"');
+}
@@ -0,0 +1,42 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use crate::ini::Ini;
+
+/// View of an [`Ini`] that records which keys are read, returned by [`Ini::with_access_tracking`].
+///
+/// This can be used to find keys that are never read, such as stale or misspelled settings.
+#[derive(Clone, Debug)]
+pub struct TrackedIni<'a> {
+    ini: &'a Ini,
+    accessed: RefCell<BTreeSet<(&'a str, &'a str)>>
+}
+
+impl Ini {
+    /// Get a view of the ini that records which keys are read.
+    pub fn with_access_tracking(&self) -> TrackedIni<'_> {
+        TrackedIni { ini: self, accessed: RefCell::new(BTreeSet::new()) }
+    }
+}
+
+impl<'a> TrackedIni<'a> {
+    /// Get the value in the section of the ini, marking the key as read.
+    ///
+    /// Returns `None` if the section or key do not exist.
+    pub fn get_value(&self, section: &str, key: &str) -> Option<&'a str> {
+        let (section, values) = self.ini.sections.get_key_value(section)?;
+        let (key, value) = values.values.get_key_value(key)?;
+        self.accessed.borrow_mut().insert((section.as_str(), key.as_str()));
+        Some(value.as_str())
+    }
+
+    /// Get the section and key of every value that has not been read.
+    pub fn unused_keys(&self) -> Vec<(&'a str, &'a str)> {
+        let accessed = self.accessed.borrow();
+        self.ini.sections
+            .iter()
+            .flat_map(|(section, values)| values.values.keys().map(move |key| (section.as_str(), key.as_str())))
+            .filter(|entry| !accessed.contains(entry))
+            .collect()
+    }
+}
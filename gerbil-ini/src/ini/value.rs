@@ -0,0 +1,139 @@
+use alloc::string::String;
+use core::fmt::{Debug, Display, Formatter};
+use core::str::FromStr;
+
+/// Borrowed value of an ini key, offering typed access.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct IniValueRef<'a>(pub &'a str);
+
+impl<'a> IniValueRef<'a> {
+    /// Get the value as a string.
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    /// Get the value as a boolean.
+    ///
+    /// `true`, `yes`, `on`, and `1` are accepted as `true`, and `false`, `no`, `off`, and `0` are accepted as `false`.
+    /// Matching is case-insensitive.
+    pub fn as_bool(&self) -> Result<bool, IniValueError> {
+        const TRUE: &[&str] = &["true", "yes", "on", "1"];
+        const FALSE: &[&str] = &["false", "no", "off", "0"];
+
+        if TRUE.iter().any(|t| t.eq_ignore_ascii_case(self.0)) {
+            Ok(true)
+        }
+        else if FALSE.iter().any(|f| f.eq_ignore_ascii_case(self.0)) {
+            Ok(false)
+        }
+        else {
+            Err(IniValueError::Invalid)
+        }
+    }
+
+    /// Get the value as a signed integer.
+    pub fn as_i64(&self) -> Result<i64, IniValueError> {
+        self.parse()
+    }
+
+    /// Get the value as a floating point number.
+    pub fn as_f64(&self) -> Result<f64, IniValueError> {
+        self.parse()
+    }
+
+    /// Get the value as a single-precision floating point number.
+    pub fn as_f32(&self) -> Result<f32, IniValueError> {
+        self.parse()
+    }
+
+    /// Get the value as an unsigned integer with an optional unit suffix, such as `5k`.
+    ///
+    /// `units` maps each suffix to the multiplier it applies. Whitespace between the number and the suffix is allowed.
+    /// A value with no suffix is not multiplied.
+    pub fn as_u64_with_unit(&self, units: &[(&str, u64)]) -> Result<u64, IniValueError> {
+        let digits_end = self.0.find(|c: char| !c.is_ascii_digit()).unwrap_or(self.0.len());
+        let (number, suffix) = self.0.split_at(digits_end);
+        let number: u64 = number.parse().map_err(|_| IniValueError::Invalid)?;
+
+        let suffix = suffix.trim_start();
+        if suffix.is_empty() {
+            return Ok(number)
+        }
+
+        let (_, multiplier) = units.iter().find(|(unit, _)| *unit == suffix).ok_or(IniValueError::UnknownUnit)?;
+        number.checked_mul(*multiplier).ok_or(IniValueError::Invalid)
+    }
+
+    fn parse<T: FromStr>(&self) -> Result<T, IniValueError> {
+        self.0.parse().map_err(|_| IniValueError::Invalid)
+    }
+}
+
+/// Describes whether non-finite floating point values are accepted.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum FloatPolicy {
+    /// Accept anything Rust can parse as a float, including `inf` and `NaN`.
+    ///
+    /// This is the default policy.
+    #[default]
+    AllowNonFinite,
+
+    /// Reject infinite and NaN values with [`IniValueError::Invalid`].
+    FiniteOnly
+}
+
+impl FloatPolicy {
+    pub(crate) fn check(self, finite: bool) -> Result<(), IniValueError> {
+        match self {
+            Self::FiniteOnly if !finite => Err(IniValueError::Invalid),
+            _ => Ok(())
+        }
+    }
+}
+
+/// An error generated when reading a value as a specific type.
+#[derive(Clone, PartialEq)]
+pub enum IniValueError {
+    Invalid,
+    UnknownUnit,
+}
+
+impl Display for IniValueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Invalid => f.write_str("Value error: The value is not valid for the requested type"),
+            Self::UnknownUnit => f.write_str("Value error: The value has an unknown unit")
+        }
+    }
+}
+
+impl Debug for IniValueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// An error generated when a value could not be parsed, as returned by [`IniSection::get_parsed_ctx`].
+///
+/// [`IniSection::get_parsed_ctx`]: crate::IniSection::get_parsed_ctx
+#[derive(Clone, PartialEq)]
+pub struct ParseFieldError {
+    /// The key whose value could not be parsed.
+    pub key: String,
+
+    /// The value that could not be parsed.
+    pub value: String
+}
+
+impl Display for ParseFieldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let Self { key, value } = self;
+        f.write_fmt(format_args!("Value error: Value `{value}` for key `{key}` could not be parsed"))
+    }
+}
+
+impl Debug for ParseFieldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
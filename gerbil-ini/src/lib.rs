@@ -35,6 +35,22 @@
 
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 mod ini;
 pub use ini::*;
 
+/// Commonly used types, for glob importing.
+///
+/// ```rust
+/// use gerbil_ini::prelude::*;
+///
+/// let options = IniOptions { mode: IniMode::SimpleTrimmed, ..Default::default() };
+/// let ini = Ini::parse_with_options("[Section]\nkey = value", &options).expect("parse");
+/// assert_eq!(ini.get_value("Section", "key"), Some("value"));
+/// ```
+pub mod prelude {
+    pub use crate::ini::{FromIniSection, Ini, IniMode, IniOptions, IniParsingError, IniSection, IniValueError};
+}
+